@@ -1,5 +1,6 @@
 #include "faigz_minimal.h"
 #include <ctype.h>
+#include <errno.h>
 #include <sys/stat.h>
 #include <unistd.h>
 
@@ -206,8 +207,42 @@ static int load_fai_index(faidx_meta_t *meta, const char *fai_path) {
 }
 
 // Public API implementation
+fai_format_options faidx_detect_format(const char *filename) {
+    if (!filename) return FAI_NONE;
+    
+    // gzopen reads plain files as-is, so this also covers gzip/BGZF input
+    gzFile fp = gzopen(filename, "r");
+    if (!fp) return FAI_NONE;
+    
+    int c;
+    do {
+        c = gzgetc(fp);
+    } while (c != -1 && isspace(c));
+    gzclose(fp);
+    
+    if (c == '>') return FAI_FASTA;
+    if (c == '@') return FAI_FASTQ;
+    return FAI_NONE;
+}
+
 faidx_meta_t *faidx_meta_load(const char *filename, fai_format_options format, int flags) {
+    return faidx_meta_load3(filename, NULL, format, flags);
+}
+
+// Destroy a half-built meta without clobbering the errno of the failed step
+static faidx_meta_t *meta_load_fail(faidx_meta_t *meta) {
+    int saved_errno = errno;
+    faidx_meta_destroy(meta);
+    errno = saved_errno;
+    return NULL;
+}
+
+// Like faidx_meta_load, with an explicit .fai path (NULL for <filename>.fai).
+// On failure errno describes the step that failed (0 if none was set).
+faidx_meta_t *faidx_meta_load3(const char *filename, const char *fai_filename,
+                               fai_format_options format, int flags) {
     if (!filename) return NULL;
+    errno = 0;
     
     faidx_meta_t *meta = calloc(1, sizeof(faidx_meta_t));
     if (!meta) return NULL;
@@ -227,7 +262,11 @@ faidx_meta_t *faidx_meta_load(const char *filename, fai_format_options format, i
     
     // Construct index paths
     char fai_path[1024];
-    snprintf(fai_path, sizeof(fai_path), "%s.fai", filename);
+    if (fai_filename) {
+        snprintf(fai_path, sizeof(fai_path), "%s", fai_filename);
+    } else {
+        snprintf(fai_path, sizeof(fai_path), "%s.fai", filename);
+    }
     meta->fai_path = str_dup(fai_path);
     
     char gzi_path[1024];
@@ -251,17 +290,14 @@ faidx_meta_t *faidx_meta_load(const char *filename, fai_format_options format, i
         if (flags & FAI_CREATE) {
             // Try to create the index
             if (create_fai_index(meta->fasta_path, meta->fai_path) < 0) {
-                faidx_meta_destroy(meta);
-                return NULL;
+                return meta_load_fail(meta);
             }
             // Now try to load the newly created index
             if (load_fai_index(meta, meta->fai_path) < 0) {
-                faidx_meta_destroy(meta);
-                return NULL;
+                return meta_load_fail(meta);
             }
         } else {
-            faidx_meta_destroy(meta);
-            return NULL;
+            return meta_load_fail(meta);
         }
     }
     
@@ -354,6 +390,19 @@ void faidx_reader_destroy(faidx_reader_t *reader) {
     free(reader);
 }
 
+// Must be called before the first fetch; returns 0 on success
+int faidx_reader_set_buffer_size(faidx_reader_t *reader, size_t size) {
+    if (!reader || size == 0) return -1;
+    
+    if (reader->gzfp) {
+        return gzbuffer(reader->gzfp, (unsigned) size);
+    }
+    if (reader->fp) {
+        return setvbuf(reader->fp, NULL, _IOFBF, size);
+    }
+    return -1;
+}
+
 char *faidx_reader_fetch_seq(faidx_reader_t *reader, const char *c_name,
                            hts_pos_t p_beg_i, hts_pos_t p_end_i, hts_pos_t *len) {
     if (!reader || !c_name) return NULL;
@@ -692,6 +741,11 @@ const char *faidx_meta_iseq(const faidx_meta_t *meta, int i) {
     return (meta && i >= 0 && i < meta->n) ? meta->name[i] : NULL;
 }
 
+// Entries are stored in index order, so position i holds sequence i
+hts_pos_t faidx_meta_iseq_len(const faidx_meta_t *meta, int i) {
+    return (meta && i >= 0 && i < meta->n) ? meta->hash->entries[i].val.len : -1;
+}
+
 hts_pos_t faidx_meta_seq_len(const faidx_meta_t *meta, const char *seq) {
     if (!meta || !seq) return -1;
     
@@ -706,6 +760,162 @@ int faidx_meta_has_seq(const faidx_meta_t *meta, const char *seq) {
     return entry != NULL;
 }
 
+// Id of the sequence named by the first len bytes of name, or -1
+static int region_name_id(const faidx_meta_t *meta, const char *name, size_t len) {
+    char *tmp = malloc(len + 1);
+    if (!tmp) return -1;
+    memcpy(tmp, name, len);
+    tmp[len] = '\0';
+    faidx1_t *entry = hash_get(meta->hash, tmp);
+    free(tmp);
+    return entry ? entry->id : -1;
+}
+
+// Maximum exponent kept while parsing; anything larger overflows any non-zero value
+#define REGION_MAX_EXPONENT 1000
+
+// Parse a decimal as htslib's hts_parse_decimal with thousands separators:
+// an optional fraction, exponent or k/M/G suffix scales the number. *strend
+// is left at str if there are no digits. Returns 0, or -1 if the value does
+// not fit in an int64_t.
+static int parse_region_decimal(const char *str, const char **strend, long long *value) {
+    long long n = 0;
+    int digits = 0, decimals = 0, e = 0;
+    char sign = '+', esign = '+';
+    const char *s;
+
+    while (isspace((unsigned char)*str)) str++;
+    s = str;
+
+    if (*s == '+' || *s == '-') sign = *s++;
+    while (*s) {
+        if (isdigit((unsigned char)*s)) {
+            int d = *s++ - '0';
+            if (n > (INT64_MAX - d) / 10) return -1;
+            n = n * 10 + d;
+            digits = 1;
+        }
+        else if (*s == ',') s++;
+        else break;
+    }
+
+    if (*s == '.') {
+        s++;
+        while (isdigit((unsigned char)*s)) {
+            int d = *s++ - '0';
+            digits = 1;
+            // Fraction digits that no longer fit only lose precision
+            if (n > (INT64_MAX - d) / 10 || decimals == REGION_MAX_EXPONENT) continue;
+            n = n * 10 + d;
+            decimals++;
+        }
+    }
+
+    switch (*s) {
+    case 'e': case 'E':
+        s++;
+        if (*s == '+' || *s == '-') esign = *s++;
+        while (isdigit((unsigned char)*s)) {
+            e = e * 10 + (*s++ - '0');
+            if (e > REGION_MAX_EXPONENT) e = REGION_MAX_EXPONENT;
+        }
+        if (esign == '-') e = -e;
+        break;
+    case 'k': case 'K': e += 3; s++; break;
+    case 'm': case 'M': e += 6; s++; break;
+    case 'g': case 'G': e += 9; s++; break;
+    }
+
+    e -= decimals;
+    for (; e > 0 && n != 0; e--) {
+        if (n > INT64_MAX / 10) return -1;
+        n *= 10;
+    }
+    while (e < 0) n /= 10, e++;
+
+    *strend = digits > 0 ? s : str;
+    *value = sign == '+' ? n : -n;
+    return 0;
+}
+
+// Parse a region string with the semantics of htslib's fai_parse_region.
+// Names may be quoted as {name} to disambiguate names containing colons.
+// Sets *tid, *beg (0-based) and *end (exclusive, INT64_MAX for "to the end")
+// and returns a pointer past the region, or NULL if the region is malformed,
+// ambiguous, has a coordinate beyond the int64_t range or names an unknown
+// sequence.
+const char *faidx_meta_parse_region(const faidx_meta_t *meta, const char *s,
+                                    int *tid, hts_pos_t *beg, hts_pos_t *end) {
+    if (!meta || !s || !tid || !beg || !end) return NULL;
+
+    size_t s_len = strlen(s);
+    const char *s_end = s + s_len;
+    const char *colon;
+
+    *beg = 0;
+    *end = INT64_MAX;
+
+    if (*s == '{') {
+        const char *close = memchr(s, '}', s_len);
+        if (!close) return NULL;
+        s++;
+        if (close[1] == '\0') {
+            *tid = region_name_id(meta, s, close - s);
+            return *tid >= 0 ? s_end : NULL;
+        }
+        if (close[1] != ':') return NULL;
+        colon = close + 1;
+        *tid = region_name_id(meta, s, close - s);
+    } else {
+        colon = strrchr(s, ':');
+        if (!colon) {
+            *tid = region_name_id(meta, s, s_len);
+            return *tid >= 0 ? s_end : NULL;
+        }
+
+        // The whole string may itself be a name, unless that is ambiguous
+        if ((*tid = region_name_id(meta, s, s_len)) >= 0) {
+            if (region_name_id(meta, s, colon - s) >= 0) return NULL;
+            return s_end;
+        }
+        *tid = region_name_id(meta, s, colon - s);
+    }
+    if (*tid < 0) return NULL;
+
+    const char *hyphen;
+    long long value;
+    if (parse_region_decimal(colon + 1, &hyphen, &value) < 0) return NULL;
+    *beg = value - 1;
+    if (*beg < 0) {
+        // Zero or negative start with an end, e.g. "chr1:-5-10"
+        if (*beg != -1 && *hyphen == '-' && colon[1] != '\0') return NULL;
+        if (isdigit((unsigned char)*hyphen) || *hyphen == '\0' || *hyphen == ',') {
+            // "chr1:-100" means chr1:1-100
+            *end = *beg == -1 ? INT64_MAX : -(*beg + 1);
+            *beg = 0;
+            return s_end;
+        }
+        if (*beg < -1) return NULL;
+        *beg = 0;
+    }
+
+    if (*hyphen == '\0') {
+        *end = INT64_MAX;
+    } else if (*hyphen == '-') {
+        if (parse_region_decimal(hyphen + 1, &hyphen, &value) < 0) return NULL;
+        *end = value;
+        if (*hyphen != '\0') return NULL;
+    } else {
+        return NULL;
+    }
+
+    // "chr1:100-" means chr1:100-<end>
+    if (*end == 0) *end = INT64_MAX;
+    if (*beg >= *end) return NULL;
+
+    return s_end;
+}
+
 faidx1_t *faidx_meta_get_entry(faidx_meta_t *meta, const char *seq_name) {
     if (!meta || !seq_name) return NULL;
     return hash_get(meta->hash, seq_name);
@@ -93,19 +93,26 @@ struct faidx_reader_t {
 };
 
 // Function declarations
+fai_format_options faidx_detect_format(const char *filename);
 faidx_meta_t *faidx_meta_load(const char *filename, fai_format_options format, int flags);
+faidx_meta_t *faidx_meta_load3(const char *filename, const char *fai_filename,
+                               fai_format_options format, int flags);
 faidx_meta_t *faidx_meta_ref(faidx_meta_t *meta);
 void faidx_meta_destroy(faidx_meta_t *meta);
 faidx_reader_t *faidx_reader_create(faidx_meta_t *meta);
 void faidx_reader_destroy(faidx_reader_t *reader);
+int faidx_reader_set_buffer_size(faidx_reader_t *reader, size_t size);
 char *faidx_reader_fetch_seq(faidx_reader_t *reader, const char *c_name,
                            hts_pos_t p_beg_i, hts_pos_t p_end_i, hts_pos_t *len);
 char *faidx_reader_fetch_qual(faidx_reader_t *reader, const char *c_name,
                             hts_pos_t p_beg_i, hts_pos_t p_end_i, hts_pos_t *len);
 int faidx_meta_nseq(const faidx_meta_t *meta);
 const char *faidx_meta_iseq(const faidx_meta_t *meta, int i);
+hts_pos_t faidx_meta_iseq_len(const faidx_meta_t *meta, int i);
 hts_pos_t faidx_meta_seq_len(const faidx_meta_t *meta, const char *seq);
 int faidx_meta_has_seq(const faidx_meta_t *meta, const char *seq);
+const char *faidx_meta_parse_region(const faidx_meta_t *meta, const char *s,
+                                    int *tid, hts_pos_t *beg, hts_pos_t *end);
 
 // BGZF support functions
 gzi_index_t *load_gzi_index(const char *gzi_path);
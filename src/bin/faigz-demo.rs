@@ -1,5 +1,8 @@
 use clap::{Parser, Subcommand};
-use faigz_rs::{FastaFormat, FastaIndex, FastaReader};
+use faigz_rs::{
+    throughput, write_fasta_record, FastaError, FastaFormat, FastaIndex, FastaReader, FastaResult,
+    Region,
+};
 use std::fs;
 
 #[derive(Parser)]
@@ -29,7 +32,8 @@ enum Commands {
     Extract {
         /// FASTA file path
         fasta: String,
-        /// Regions to extract (format: chr:start-end or chr for whole sequence)
+        /// Regions to extract (format: chr:start-end, chr:pos from pos to the end,
+        /// chr for whole sequence, or a glob like chr* for every matching whole sequence)
         /// Uses 0-based half-open coordinates like bedtools (start inclusive, end exclusive)
         regions: Vec<String>,
         /// Use 1-based coordinates like samtools faidx instead of 0-based
@@ -143,65 +147,31 @@ fn extract_sequences(
 ) -> Result<(), Box<dyn std::error::Error>> {
     let index = FastaIndex::new(fasta, FastaFormat::Fasta)?;
     let reader = FastaReader::new(&index)?;
+    let mut out = std::io::stdout().lock();
 
     for region in regions {
-        let result = if region.contains(':') {
-            // Parse region like chr1:100-200
-            let parts: Vec<&str> = region.split(':').collect();
-            if parts.len() != 2 {
-                eprintln!("Invalid region format: {}", region);
-                continue;
-            }
-
-            let chr = parts[0];
-            let range = parts[1];
-
-            if range.contains('-') {
-                let range_parts: Vec<&str> = range.split('-').collect();
-                if range_parts.len() != 2 {
-                    eprintln!("Invalid range format: {}", range);
-                    continue;
+        if region.contains(['*', '?']) {
+            // Glob over sequence names, extracting each match in full
+            for (name, result) in reader.fetch_glob(region) {
+                match result {
+                    Ok(sequence) => {
+                        write_fasta_record(&mut out, &name, sequence.as_bytes(), 80)?;
+                    }
+                    Err(e) => {
+                        eprintln!("Error extracting {}: {}", name, e);
+                    }
                 }
-
-                let start: i64 = range_parts[0]
-                    .parse()
-                    .map_err(|e| format!("Invalid start position '{}': {}", range_parts[0], e))?;
-                let end: i64 = range_parts[1]
-                    .parse()
-                    .map_err(|e| format!("Invalid end position '{}': {}", range_parts[1], e))?;
-
-                // Convert coordinates based on system
-                let (actual_start, actual_end) = if one_based {
-                    // samtools faidx uses 1-based inclusive coordinates
-                    // Convert to 0-based half-open
-                    (start - 1, end)
-                } else {
-                    // bedtools uses 0-based half-open coordinates (start inclusive, end exclusive)
-                    (start, end)
-                };
-
-                reader.fetch_seq(chr, actual_start, actual_end)
-            } else {
-                // Single position
-                let pos: i64 = range
-                    .parse()
-                    .map_err(|e| format!("Invalid position '{}': {}", range, e))?;
-
-                let actual_pos = if one_based { pos - 1 } else { pos };
-                reader.fetch_seq(chr, actual_pos, actual_pos + 1)
             }
-        } else {
-            // Whole sequence
-            reader.fetch_seq_all(region)
-        };
+            continue;
+        }
+
+        let result = parse_cli_region(&index, region, one_based)
+            .and_then(|parsed| fetch_parsed_region(&index, &reader, &parsed));
 
         match result {
             Ok(sequence) => {
-                println!(">{}", region);
                 // Print sequence in 80-character lines like standard FASTA
-                for line in sequence.as_bytes().chunks(80) {
-                    println!("{}", String::from_utf8_lossy(line));
-                }
+                write_fasta_record(&mut out, region, sequence.as_bytes(), 80)?;
             }
             Err(e) => {
                 eprintln!("Error extracting {}: {}", region, e);
@@ -212,19 +182,40 @@ fn extract_sequences(
     Ok(())
 }
 
+/// Parse a command-line region into 1-based inclusive coordinates
+///
+/// Coordinates are read as 1-based inclusive (samtools) or 0-based half-open
+/// (bedtools). As with [`FastaReader::fetch_region`], a single position
+/// (`chr1:500`) runs to the end of the sequence.
+fn parse_cli_region(index: &FastaIndex, region: &str, one_based: bool) -> FastaResult<Region> {
+    let mut parsed = index.parse_region(region)?;
+    if !one_based {
+        parsed.start = parsed.start.map(|start| start + 1);
+    }
+    Ok(parsed)
+}
+
+/// Fetch a region from [`parse_cli_region`], clamped to the sequence end
+fn fetch_parsed_region(
+    index: &FastaIndex,
+    reader: &FastaReader,
+    region: &Region,
+) -> FastaResult<String> {
+    let length = index
+        .sequence_length(&region.name)
+        .ok_or_else(|| FastaError::SequenceNotFound(region.name.clone()))?;
+    let (start, end) = region.bounds(length);
+    reader.fetch_seq(&region.name, start, end.min(length))
+}
+
 fn thread_test(
     fasta: &str,
     num_threads: usize,
     operations: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    use std::sync::Arc;
-    use std::thread;
-    use std::time::Instant;
-
-    let index = Arc::new(FastaIndex::new(fasta, FastaFormat::Fasta)?);
-    let sequences = index.sequence_names();
+    let index = FastaIndex::new(fasta, FastaFormat::Fasta)?;
 
-    if sequences.is_empty() {
+    if index.num_sequences() == 0 {
         return Err("No sequences found in FASTA file".into());
     }
 
@@ -233,63 +224,30 @@ fn thread_test(
         num_threads, operations
     );
 
-    let start = Instant::now();
-    let mut handles = vec![];
-
-    for thread_id in 0..num_threads {
-        let index_clone = Arc::clone(&index);
-        let sequences_clone = sequences.clone();
-
-        let handle = thread::spawn(move || {
-            let reader = FastaReader::new(&index_clone).unwrap();
-            let mut success_count = 0;
-
-            for i in 0..operations {
-                let seq_name = &sequences_clone[i % sequences_clone.len()];
-                let seq_len = index_clone.sequence_length(seq_name).unwrap_or(0);
-
-                if seq_len > 10 {
-                    // Extract a small region
-                    let start = (i as i64) % (seq_len - 10);
-                    let end = start + 10;
-
-                    match reader.fetch_seq(seq_name, start, end) {
-                        Ok(seq) => {
-                            if seq.len() == 10 {
-                                success_count += 1;
-                            }
-                        }
-                        Err(_) => {}
-                    }
-                }
-            }
-
-            (thread_id, success_count)
-        });
+    let report = throughput(&index, num_threads, operations)?;
 
-        handles.push(handle);
-    }
-
-    let mut total_success = 0;
-    for handle in handles {
-        let (thread_id, success_count) = handle.join().unwrap();
+    for thread in &report.threads {
         println!(
-            "Thread {}: {}/{} successful extractions",
-            thread_id, success_count, operations
+            "Thread {}: {}/{} successful extractions (p50 {:?}, p95 {:?}, p99 {:?})",
+            thread.thread_id,
+            thread.successes,
+            thread.operations,
+            thread.latency.p50,
+            thread.latency.p95,
+            thread.latency.p99
         );
-        total_success += success_count;
     }
 
-    let duration = start.elapsed();
     println!(
         "\nTotal: {}/{} successful extractions",
-        total_success,
-        num_threads * operations
+        report.successes(),
+        report.operations()
     );
-    println!("Time: {:?}", duration);
+    println!("Time: {:?}", report.elapsed);
+    println!("Rate: {:.2} extractions/second", report.rate());
     println!(
-        "Rate: {:.2} extractions/second",
-        total_success as f64 / duration.as_secs_f64()
+        "Latency: p50 {:?}, p95 {:?}, p99 {:?}",
+        report.latency.p50, report.latency.p95, report.latency.p99
     );
 
     Ok(())
@@ -306,48 +264,20 @@ fn compare_with_samtools(
     let index = FastaIndex::new(fasta, FastaFormat::Fasta)?;
     let reader = FastaReader::new(&index)?;
 
-    let faigz_result = if region.contains(':') {
-        let parts: Vec<&str> = region.split(':').collect();
-        let chr = parts[0];
-        let range = parts[1];
-        let range_parts: Vec<&str> = range.split('-').collect();
-        let start: i64 = range_parts[0].parse()?;
-        let end: i64 = range_parts[1].parse()?;
-
-        let (actual_start, actual_end) = if one_based {
-            (start - 1, end)
-        } else {
-            (start, end)
-        };
-
-        reader.fetch_seq(chr, actual_start, actual_end)?
-    } else {
-        reader.fetch_seq_all(region)?
-    };
+    let parsed = parse_cli_region(&index, region, one_based)?;
+    let faigz_result = fetch_parsed_region(&index, &reader, &parsed)?;
 
     println!("=== faigz-rs result ===");
-    println!(">{}", region);
-    for line in faigz_result.as_bytes().chunks(80) {
-        println!("{}", String::from_utf8_lossy(line));
-    }
+    write_fasta_record(
+        &mut std::io::stdout().lock(),
+        region,
+        faigz_result.as_bytes(),
+        80,
+    )?;
 
     // Try to compare with samtools faidx if available
-    let samtools_region = if one_based {
-        region.to_string()
-    } else {
-        // Convert 0-based to 1-based for samtools
-        if region.contains(':') {
-            let parts: Vec<&str> = region.split(':').collect();
-            let chr = parts[0];
-            let range = parts[1];
-            let range_parts: Vec<&str> = range.split('-').collect();
-            let start: i64 = range_parts[0].parse()?;
-            let end: i64 = range_parts[1].parse()?;
-            format!("{}:{}-{}", chr, start + 1, end)
-        } else {
-            region.to_string()
-        }
-    };
+    // samtools takes the 1-based inclusive form
+    let samtools_region = parsed.to_string();
 
     match Command::new("samtools")
         .args(["faidx", fasta, &samtools_region])
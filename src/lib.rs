@@ -30,9 +30,12 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_int, c_void};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use thiserror::Error;
 
 // Include the generated bindings
@@ -54,19 +57,105 @@ pub enum FastaError {
     SequenceNotFound(String),
     #[error("Invalid region: {0}")]
     InvalidRegion(String),
+    #[error("Invalid region: {seqname}:{start}-{end}")]
+    InvalidCoordinates {
+        seqname: String,
+        start: i64,
+        end: i64,
+    },
     #[error("Memory allocation failed")]
     MemoryError,
+    /// An I/O failure reported by the C reader, which gives no underlying
+    /// error: failed sequence or quality reads and fetch timeouts. Failures
+    /// with an `std::io::Error` use [`FastaError::Io`].
     #[error("I/O error: {0}")]
     IoError(String),
+    /// An I/O failure with the underlying error kept as its source
+    #[error("I/O error: {context}: {source}")]
+    Io {
+        context: String,
+        source: std::io::Error,
+    },
     #[error("Quality data not available (FASTA format)")]
     QualityNotAvailable,
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Non-ACGT base '{0}' at position {1}")]
+    NonAcgtBase(char, i64),
+    #[error("Index inconsistent with FASTA file: {0}")]
+    InconsistentIndex(String),
+    #[error(
+        "{0}: compressed with gzip, not bgzip; recompress with: gunzip -c {0} | bgzip > <out>.gz"
+    )]
+    NotBgzf(String),
+    #[error("Invalid quality character '{0}' at position {1}")]
+    InvalidQuality(char, i64),
+    /// A FASTQ region whose sequence and quality strings differ in length
+    #[error("Sequence and quality lengths differ: {seq_len} bases, {qual_len} qualities")]
+    LengthMismatch { seq_len: usize, qual_len: usize },
+    #[error(
+        "Region {seqname}:{start}-{end} out of bounds for sequence of length {length}{}",
+        suggestion_hint(.suggested)
+    )]
+    OutOfBounds {
+        seqname: String,
+        start: i64,
+        end: i64,
+        length: i64,
+        /// The region clamped to the sequence, if any of it overlaps
+        suggested: Option<(i64, i64)>,
+    },
+}
+
+fn suggestion_hint(suggested: &Option<(i64, i64)>) -> String {
+    match suggested {
+        Some((start, end)) => format!(" (valid region: {}-{})", start, end),
+        None => String::new(),
+    }
+}
+
+impl FastaError {
+    /// Whether the operation that produced this error may succeed if retried
+    ///
+    /// Only transient failures are retryable: I/O errors and failure to open a
+    /// reader's file handle (e.g. when file descriptors are exhausted). Errors
+    /// caused by the request itself (missing sequences, invalid regions or
+    /// parameters) or by the files on disk (missing, unreadable or
+    /// inconsistent files and indexes) are fatal and will fail again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FastaError::IoError(_) | FastaError::ReaderCreationError => true,
+            FastaError::Io { source, .. } => !matches!(
+                source.kind(),
+                std::io::ErrorKind::NotFound
+                    | std::io::ErrorKind::PermissionDenied
+                    | std::io::ErrorKind::InvalidInput
+                    | std::io::ErrorKind::InvalidData
+                    | std::io::ErrorKind::Unsupported
+            ),
+            FastaError::InvalidPath(_)
+            | FastaError::IndexLoadError(_)
+            | FastaError::SequenceNotFound(_)
+            | FastaError::InvalidRegion(_)
+            | FastaError::InvalidCoordinates { .. }
+            | FastaError::MemoryError
+            | FastaError::QualityNotAvailable
+            | FastaError::InvalidParameter(_)
+            | FastaError::NonAcgtBase(_, _)
+            | FastaError::InconsistentIndex(_)
+            | FastaError::NotBgzf(_)
+            | FastaError::InvalidQuality(_, _)
+            | FastaError::LengthMismatch { .. }
+            | FastaError::OutOfBounds { .. } => false,
+        }
+    }
 }
 
 /// Result type for FASTA operations
 pub type FastaResult<T> = Result<T, FastaError>;
 
 /// Format options for FASTA/FASTQ files
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FastaFormat {
     /// FASTA format
     Fasta,
@@ -83,283 +172,4860 @@ impl From<FastaFormat> for fai_format_options {
     }
 }
 
-/// Shared FASTA index metadata
-///
-/// This structure holds the shared metadata for a FASTA/FASTQ file that can be
-/// safely accessed from multiple threads. It uses reference counting to manage
-/// the lifetime of the underlying C structure.
-pub struct FastaIndex {
-    meta: *mut faidx_meta_t,
+/// Nucleic acid alphabet used to normalize fetched sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NucleicAcid {
+    /// DNA alphabet (U is reverse-transcribed to T)
+    Dna,
+    /// RNA alphabet (T is transcribed to U)
+    Rna,
 }
 
-impl std::fmt::Debug for FastaIndex {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("FastaIndex")
-            .field("num_sequences", &self.num_sequences())
-            .field("sequence_names", &self.sequence_names())
-            .finish()
-    }
+/// Strand of a region relative to the reference sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strand {
+    /// The reference (`+`) strand
+    Forward,
+    /// The reverse-complement (`-`) strand
+    Reverse,
 }
 
-impl FastaIndex {
-    /// Create a new FASTA index from a file path
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - Path to the FASTA/FASTQ file
-    /// * `format` - Format of the file (FASTA or FASTQ)
-    ///
-    /// # Returns
-    ///
-    /// A new `FastaIndex` instance or an error if the file cannot be loaded
-    pub fn new(path: &str, format: FastaFormat) -> FastaResult<Self> {
-        let c_path = CString::new(path).map_err(|_| FastaError::InvalidPath(path.to_string()))?;
+/// Handling of the windows that run past the end of a sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartialWindow {
+    /// Yield them truncated at the sequence end
+    Truncate,
+    /// Leave them out, so every window has the full size
+    Skip,
+}
 
-        // Pass 0 (no flags) to only load existing index, never create
-        // This prevents trying to create index by reading bgzip files as plain text
-        let meta = unsafe { faidx_meta_load(c_path.as_ptr(), format.into(), 0) };
+/// When [`FastaIndexBuilder`] builds the `.fai` from the FASTA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexCreation {
+    /// Never build it; the index must already exist
+    Never,
+    /// Build it only if it does not exist yet
+    IfMissing,
+    /// Always rebuild it, replacing an existing (possibly stale) index
+    Always,
+}
 
-        if meta.is_null() {
-            return Err(FastaError::IndexLoadError(format!(
-                "{}: Index file not found or failed to load. \
-                Create index with: samtools faidx {}",
-                path, path
-            )));
-        }
+/// Placement of a fixed-width window relative to its anchor position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    /// The window is centered on the position (extra base to the right for even widths)
+    Center,
+    /// The window starts at the position
+    Left,
+    /// The window ends just before the position
+    Right,
+}
 
-        Ok(FastaIndex { meta })
-    }
+/// ASCII offset encoding of FASTQ quality scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityEncoding {
+    /// Sanger / Illumina 1.8+ (ASCII offset 33)
+    Phred33,
+    /// Illumina 1.3-1.7 (ASCII offset 64)
+    Phred64,
+    /// The sampled qualities are valid under both encodings
+    Ambiguous,
+}
 
-    /// Get the number of sequences in the index
-    pub fn num_sequences(&self) -> usize {
-        unsafe { faidx_meta_nseq(self.meta) as usize }
+/// Occurrences of one base, split by soft-masking state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BaseCount {
+    /// Occurrences in uppercase (unmasked) form
+    pub unmasked: usize,
+    /// Occurrences in lowercase (soft-masked) form
+    pub masked: usize,
+}
+
+impl BaseCount {
+    /// Total occurrences regardless of masking
+    pub fn total(&self) -> usize {
+        self.unmasked + self.masked
     }
+}
 
-    /// Get the name of the sequence at the given index
-    pub fn sequence_name(&self, index: usize) -> Option<String> {
-        let name_ptr = unsafe { faidx_meta_iseq(self.meta, index as c_int) };
-        if name_ptr.is_null() {
-            None
-        } else {
-            let c_str = unsafe { CStr::from_ptr(name_ptr) };
-            Some(c_str.to_string_lossy().to_string())
-        }
+/// Base composition of a region
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BaseCounts {
+    /// Adenine
+    pub a: BaseCount,
+    /// Cytosine
+    pub c: BaseCount,
+    /// Guanine
+    pub g: BaseCount,
+    /// Thymine (or uracil)
+    pub t: BaseCount,
+    /// Unknown base
+    pub n: BaseCount,
+    /// Strong base (G or C)
+    pub s: BaseCount,
+    /// Any other character (other IUPAC ambiguity codes, gaps)
+    pub other: BaseCount,
+}
+
+/// Genome-wide base composition returned by [`FastaReader::genome_composition`]
+pub type Composition = BaseCounts;
+
+impl BaseCounts {
+    /// Total number of bases counted
+    pub fn total(&self) -> usize {
+        self.bases().iter().map(BaseCount::total).sum()
     }
 
-    /// Get the length of the specified sequence
-    pub fn sequence_length(&self, name: &str) -> Option<i64> {
-        let c_name = CString::new(name).ok()?;
-        let length = unsafe { faidx_meta_seq_len(self.meta, c_name.as_ptr()) };
-        if length < 0 {
-            None
-        } else {
-            Some(length)
-        }
+    /// Number of soft-masked bases counted
+    pub fn masked(&self) -> usize {
+        self.bases().iter().map(|count| count.masked).sum()
     }
 
-    /// Check if the index contains the specified sequence
-    pub fn has_sequence(&self, name: &str) -> bool {
-        let c_name = CString::new(name).unwrap_or_else(|_| CString::new("").unwrap());
-        unsafe { faidx_meta_has_seq(self.meta, c_name.as_ptr()) != 0 }
+    /// Fraction of bases that are soft-masked, or 0 for an empty region
+    pub fn masked_fraction(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.masked() as f64 / total as f64,
+        }
     }
 
-    /// Get all sequence names in the index
-    pub fn sequence_names(&self) -> Vec<String> {
-        let mut names = Vec::new();
-        let n = self.num_sequences();
-        for i in 0..n {
-            if let Some(name) = self.sequence_name(i) {
-                names.push(name);
-            }
+    /// Fraction of G, C and S among all bases except N, or 0 if there are none
+    pub fn gc_fraction(&self) -> f64 {
+        match self.total() - self.n.total() {
+            0 => 0.0,
+            called => (self.g.total() + self.c.total() + self.s.total()) as f64 / called as f64,
         }
-        names
     }
-}
 
-impl Clone for FastaIndex {
-    fn clone(&self) -> Self {
-        let meta = unsafe { faidx_meta_ref(self.meta) };
-        FastaIndex { meta }
+    fn bases(&self) -> [BaseCount; 7] {
+        [self.a, self.c, self.g, self.t, self.n, self.s, self.other]
     }
-}
 
-impl Drop for FastaIndex {
-    fn drop(&mut self) {
-        unsafe {
-            faidx_meta_destroy(self.meta);
+    /// Tally a sequence, recording lowercase bases as masked if `split_masked`
+    fn tally(&mut self, seq: &[u8], split_masked: bool) {
+        for &base in seq {
+            let count = match base.to_ascii_uppercase() {
+                b'A' => &mut self.a,
+                b'C' => &mut self.c,
+                b'G' => &mut self.g,
+                b'T' | b'U' => &mut self.t,
+                b'N' => &mut self.n,
+                b'S' => &mut self.s,
+                _ => &mut self.other,
+            };
+            if split_masked && base.is_ascii_lowercase() {
+                count.masked += 1;
+            } else {
+                count.unmasked += 1;
+            }
         }
     }
 }
 
-unsafe impl Send for FastaIndex {}
-unsafe impl Sync for FastaIndex {}
-
-/// FASTA reader for accessing sequences
+/// A region string such as "chr1:1,001-2,000", parsed into its parts
 ///
-/// This structure provides thread-safe access to FASTA/FASTQ sequences using
-/// a shared index. Each reader maintains its own file handle but shares the
-/// index metadata.
-pub struct FastaReader {
-    reader: *mut faidx_reader_t,
-    _index: Arc<FastaIndex>, // Keep index alive
+/// Coordinates are kept as written: 1-based and inclusive, as in samtools.
+/// Either bound may be missing: "chr1:1000" and "chr1:1000-" have no end,
+/// "chr1:-2000" has no start, and a bare "chr1" has neither.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Sequence name
+    pub name: String,
+    /// First base (1-based, inclusive), or `None` for the sequence start
+    pub start: Option<i64>,
+    /// Last base (1-based, inclusive), or `None` for the sequence end
+    pub end: Option<i64>,
 }
 
-impl FastaReader {
-    /// Create a new FASTA reader from an index
+impl Region {
+    /// Parse a region string
+    ///
+    /// The string is split at its last colon when the part after it looks
+    /// like a coordinate range. Thousands separators and spaces around the
+    /// coordinates are ignored. Names that themselves contain a colon and
+    /// end in digits are ambiguous without an index; use
+    /// [`FastaReader::fetch_region`], which checks the names it knows about.
     ///
     /// # Arguments
     ///
-    /// * `index` - Shared FASTA index
+    /// * `region` - Region string in format "seqname:start-end"
     ///
     /// # Returns
     ///
-    /// A new `FastaReader` instance or an error if the reader cannot be created
-    pub fn new(index: &FastaIndex) -> FastaResult<Self> {
-        let reader = unsafe { faidx_reader_create(index.meta) };
+    /// The parsed region or an `InvalidRegion` error
+    pub fn parse(region: &str) -> FastaResult<Region> {
+        Self::parse_with(region, |_| false)
+    }
 
-        if reader.is_null() {
-            return Err(FastaError::ReaderCreationError);
+    /// Parse a region string, keeping any string accepted by `has_sequence` whole
+    fn parse_with(region: &str, has_sequence: impl Fn(&str) -> bool) -> FastaResult<Region> {
+        let invalid = || FastaError::InvalidRegion(region.to_string());
+        let Some((name, range_part)) = split_region(region, has_sequence) else {
+            if region.is_empty() {
+                return Err(invalid());
+            }
+            return Ok(Region {
+                name: region.to_string(),
+                start: None,
+                end: None,
+            });
+        };
+        if name.is_empty() {
+            return Err(invalid());
         }
 
-        Ok(FastaReader {
-            reader,
-            _index: Arc::new(index.clone()),
+        let (start_str, end_str) = match range_part.split_once('-') {
+            Some((start_str, end_str)) => (start_str, end_str),
+            None => (range_part, ""),
+        };
+        // Coordinates copied from genome browsers carry thousands separators
+        let parse_coordinate = |coordinate: &str| {
+            let coordinate = coordinate.trim();
+            if coordinate.is_empty() {
+                return Ok(None);
+            }
+            coordinate
+                .replace(',', "")
+                .parse::<i64>()
+                .map(Some)
+                .map_err(|_| invalid())
+        };
+
+        Ok(Region {
+            name: name.to_string(),
+            start: parse_coordinate(start_str)?,
+            end: parse_coordinate(end_str)?,
         })
     }
 
-    /// Fetch a sequence from the specified region
+    /// Convert to 0-based, half-open coordinates
     ///
     /// # Arguments
     ///
-    /// * `seqname` - Name of the sequence
-    /// * `start` - Start position (0-based, inclusive)
-    /// * `end` - End position (0-based, exclusive)
+    /// * `length` - Length of the sequence, used for a missing end
     ///
     /// # Returns
     ///
-    /// The sequence string or an error if the sequence cannot be fetched
-    pub fn fetch_seq(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
-        let c_seqname =
-            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+    /// The `(start, end)` pair to pass to [`FastaReader::fetch_seq`]
+    pub fn bounds(&self, length: i64) -> (i64, i64) {
+        (
+            self.start.map_or(0, |start| start - 1),
+            self.end.unwrap_or(length),
+        )
+    }
+}
 
-        let mut len: i64 = 0;
-        let seq_ptr = unsafe {
-            faidx_reader_fetch_seq(self.reader, c_seqname.as_ptr(), start, end, &mut len)
-        };
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        match (self.start, self.end) {
+            (None, None) => Ok(()),
+            (Some(start), None) => write!(f, ":{}-", start),
+            (None, Some(end)) => write!(f, ":-{}", end),
+            (Some(start), Some(end)) => write!(f, ":{}-{}", start, end),
+        }
+    }
+}
 
-        if seq_ptr.is_null() {
-            return Err(FastaError::SequenceNotFound(seqname.to_string()));
+/// A single interval from a BED file
+///
+/// Coordinates are 0-based and half-open, as in the BED format. Optional
+/// columns that are absent from the file are `None` (or `'.'` for the strand).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BedRecord {
+    /// Sequence name (column 1)
+    pub chrom: String,
+    /// Start position (column 2, 0-based, inclusive)
+    pub start: i64,
+    /// End position (column 3, 0-based, exclusive)
+    pub end: i64,
+    /// Feature name (column 4)
+    pub name: Option<String>,
+    /// Score (column 5)
+    pub score: Option<String>,
+    /// Strand (column 6): `'+'`, `'-'` or `'.'`
+    pub strand: char,
+}
+
+impl BedRecord {
+    /// Parse a BED data line, returning `None` for blank, comment, and header lines
+    fn parse_line(line: &str) -> Option<FastaResult<Self>> {
+        let line = line.trim_end();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("track")
+            || line.starts_with("browser")
+        {
+            return None;
         }
 
-        let c_str = unsafe { CStr::from_ptr(seq_ptr) };
-        let result = c_str.to_string_lossy().to_string();
+        let invalid = || FastaError::InvalidRegion(line.to_string());
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 3 {
+            return Some(Err(invalid()));
+        }
 
-        unsafe {
-            libc::free(seq_ptr as *mut c_void);
+        let record = (|| {
+            let strand = match fields.get(5) {
+                Some(&"+") => '+',
+                Some(&"-") => '-',
+                Some(&".") | None => '.',
+                Some(_) => return Err(invalid()),
+            };
+            Ok(BedRecord {
+                chrom: fields[0].to_string(),
+                start: fields[1].parse().map_err(|_| invalid())?,
+                end: fields[2].parse().map_err(|_| invalid())?,
+                name: fields.get(3).map(|s| s.to_string()),
+                score: fields.get(4).map(|s| s.to_string()),
+                strand,
+            })
+        })();
+        Some(record)
+    }
+
+    /// Strand to read the interval from; `'.'` reads the forward strand
+    fn read_strand(&self) -> Strand {
+        if self.strand == '-' {
+            Strand::Reverse
+        } else {
+            Strand::Forward
         }
+    }
 
-        Ok(result)
+    /// The feature name, or `chrom:start-end` in BED coordinates when unnamed
+    fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{}:{}-{}", self.chrom, self.start, self.end),
+        }
     }
+}
 
-    /// Fetch the entire sequence
+/// Read all intervals from a BED file
+fn read_bed(path: &str) -> FastaResult<Vec<BedRecord>> {
+    let contents = std::fs::read_to_string(path).map_err(|source| FastaError::Io {
+        context: path.to_string(),
+        source,
+    })?;
+    contents.lines().filter_map(BedRecord::parse_line).collect()
+}
+
+/// Metadata of one sequence in the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceRecord {
+    /// Sequence name
+    pub name: String,
+    /// Sequence length in bases
+    pub length: i64,
+}
+
+/// Sequence names and lengths of an index, detached from the FASTA
+///
+/// Obtained from [`FastaIndex::metadata`]. With the `serde` feature it can be
+/// serialized, e.g. to a JSON sidecar for services that validate coordinates
+/// without opening the FASTA.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexMetadata {
+    /// Every sequence in index order
+    pub sequences: Vec<SequenceRecord>,
+}
+
+/// A fetched sequence with its name and, for FASTQ, its qualities
+///
+/// Obtained from [`FastaReader::fetch_record_named`]. Its `Display`
+/// implementation writes an unwrapped FASTA record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastaRecord {
+    /// Header written after `>` or `@`
+    pub name: String,
+    /// Sequence bases
+    pub seq: String,
+    /// Quality string, `None` for records fetched from FASTA
+    pub qual: Option<String>,
+}
+
+impl FastaRecord {
+    /// Format the record as FASTA
     ///
     /// # Arguments
     ///
-    /// * `seqname` - Name of the sequence
+    /// * `line_width` - Number of bases per line (0 for no wrapping)
     ///
     /// # Returns
     ///
-    /// The complete sequence string or an error if the sequence cannot be fetched
-    pub fn fetch_seq_all(&self, seqname: &str) -> FastaResult<String> {
-        let length = self
-            ._index
-            .sequence_length(seqname)
-            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+    /// The header line followed by the wrapped sequence, newline-terminated
+    pub fn to_fasta_string(&self, line_width: usize) -> String {
+        let mut out = Vec::with_capacity(self.name.len() + self.seq.len() + 2);
+        write_fasta_record(&mut out, &self.name, self.seq.as_bytes(), line_width)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(out)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+    }
 
-        self.fetch_seq(seqname, 0, length)
+    /// Format the record as four-line FASTQ
+    ///
+    /// Records without qualities are given the lowest quality, `!`, for every base.
+    pub fn to_fastq_string(&self) -> String {
+        let qual = match &self.qual {
+            Some(qual) => qual.clone(),
+            None => "!".repeat(self.seq.len()),
+        };
+        format!("@{}\n{}\n+\n{}\n", self.name, self.seq, qual)
     }
+}
 
-    /// Fetch quality scores for the specified region (FASTQ only)
+impl std::fmt::Display for FastaRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_fasta_string(0))
+    }
+}
+
+/// Shared FASTA index metadata
+///
+/// This structure holds the shared metadata for a FASTA/FASTQ file that can be
+/// safely accessed from multiple threads. It uses reference counting to manage
+/// the lifetime of the underlying C structure.
+pub struct FastaIndex {
+    meta: *mut faidx_meta_t,
+    format: FastaFormat,
+    // Sorted ids of the visible sequences when opened with `new_filtered`
+    selected: Option<Arc<Vec<usize>>>,
+    // Sequence names keyed by MD5 digest, computed on first `find_by_md5`
+    digests: Arc<OnceLock<HashMap<String, String>>>,
+    // Sum of the visible sequence lengths, computed on first `total_length`
+    total_length: Arc<OnceLock<i64>>,
+    // File read buffer size applied to every reader, set through the builder
+    read_buffer_size: Option<usize>,
+    // Mapping of an uncompressed FASTA that readers fetch from, set through the builder
+    mmap: Option<Arc<FastaMmap>>,
+    // Helper threads of timeout-bounded fetches still running, across all readers
+    pending_fetches: Arc<AtomicUsize>,
+}
+
+impl std::fmt::Debug for FastaIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FastaIndex")
+            .field("num_sequences", &self.num_sequences())
+            .field("sequence_names", &self.sequence_names())
+            .finish()
+    }
+}
+
+/// Error for the last `faidx_meta_load*` call returning null
+///
+/// The loader leaves `errno` set by the step that failed, so this must be
+/// called right after the FFI call. An OS error is kept as the source of a
+/// [`FastaError::Io`]; without one, the failure is an
+/// [`FastaError::IndexLoadError`] giving `reason`.
+fn load_failure(context: String, reason: &str) -> FastaError {
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(code) if code != 0 => FastaError::Io {
+            context,
+            source: err,
+        },
+        _ => FastaError::IndexLoadError(format!("{}: {}", context, reason)),
+    }
+}
+
+/// Check that `path` is a regular file, describing it as `what` otherwise
+fn require_file(path: &str, what: &str) -> FastaResult<()> {
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => Ok(()),
+        Ok(_) => Err(FastaError::IndexLoadError(format!(
+            "{}: {} is not a regular file",
+            path, what
+        ))),
+        Err(source) => Err(FastaError::Io {
+            context: format!("{}: {}", path, what),
+            source,
+        }),
+    }
+}
+
+impl FastaIndex {
+    /// Create a new FASTA index from a file path
     ///
     /// # Arguments
     ///
-    /// * `seqname` - Name of the sequence
-    /// * `start` - Start position (0-based, inclusive)
-    /// * `end` - End position (0-based, exclusive)
+    /// * `path` - Path to the FASTA/FASTQ file
+    /// * `format` - Format of the file (FASTA or FASTQ)
     ///
     /// # Returns
     ///
-    /// The quality string or an error if the quality cannot be fetched
-    pub fn fetch_qual(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
-        let c_seqname =
-            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+    /// A new `FastaIndex` instance or an error if the file cannot be loaded
+    pub fn new(path: &str, format: FastaFormat) -> FastaResult<Self> {
+        let c_path = CString::new(path).map_err(|_| FastaError::InvalidPath(path.to_string()))?;
 
-        let mut len: i64 = 0;
-        let qual_ptr = unsafe {
-            faidx_reader_fetch_qual(self.reader, c_seqname.as_ptr(), start, end - 1, &mut len)
-        };
+        // Pass 0 (no flags) to only load existing index, never create
+        // This prevents trying to create index by reading bgzip files as plain text
+        let meta = unsafe { faidx_meta_load(c_path.as_ptr(), format.into(), 0) };
 
-        if qual_ptr.is_null() {
-            return Err(FastaError::QualityNotAvailable);
+        if meta.is_null() {
+            return Err(load_failure(
+                format!("{} (create index with: samtools faidx {})", path, path),
+                "index file not found or failed to load",
+            ));
         }
 
-        let c_str = unsafe { CStr::from_ptr(qual_ptr) };
-        let result = c_str.to_string_lossy().to_string();
+        Self::from_meta(meta, format)
+    }
 
-        unsafe {
-            libc::free(qual_ptr as *mut c_void);
+    /// Create a new index, detecting FASTA or FASTQ from the file contents
+    ///
+    /// The format is taken from the first non-whitespace character: `>` for
+    /// FASTA and `@` for FASTQ. Gzip and BGZF-compressed files are
+    /// decompressed to inspect it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the FASTA/FASTQ file
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaIndex` instance or an error if the file cannot be read,
+    /// its format cannot be detected, or it cannot be loaded
+    pub fn new_auto(path: &str) -> FastaResult<Self> {
+        if let Err(source) = std::fs::File::open(path) {
+            return Err(FastaError::Io {
+                context: path.to_string(),
+                source,
+            });
         }
+        let c_path = CString::new(path).map_err(|_| FastaError::InvalidPath(path.to_string()))?;
 
-        Ok(result)
+        let format = match unsafe { faidx_detect_format(c_path.as_ptr()) } {
+            detected if detected == fai_format_options_FAI_FASTA => FastaFormat::Fasta,
+            detected if detected == fai_format_options_FAI_FASTQ => FastaFormat::Fastq,
+            _ => {
+                return Err(FastaError::IndexLoadError(format!(
+                "{}: cannot detect format, file starts with neither '>' (FASTA) nor '@' (FASTQ)",
+                path
+            )))
+            }
+        };
+
+        Self::new(path, format)
     }
 
-    /// Parse a region string (e.g., "chr1:1000-2000") and fetch the sequence
+    /// Create a new FASTA index using a `.fai` stored apart from the FASTA
+    ///
+    /// Useful when the FASTA lives on a read-only mount and its index is kept
+    /// elsewhere. Any `.gzi` is still looked up next to the FASTA.
     ///
     /// # Arguments
     ///
-    /// * `region` - Region string in format "seqname:start-end"
+    /// * `fasta_path` - Path to the FASTA/FASTQ file
+    /// * `fai_path` - Path to the `.fai` index of the file
+    /// * `format` - Format of the file (FASTA or FASTQ)
     ///
     /// # Returns
     ///
-    /// The sequence string or an error if the region cannot be parsed or fetched
-    pub fn fetch_region(&self, region: &str) -> FastaResult<String> {
-        // Simple region parsing - you might want to use the C function for more complex cases
-        if let Some(colon_pos) = region.find(':') {
-            let seqname = &region[..colon_pos];
-            let range_part = &region[colon_pos + 1..];
-
-            if let Some(dash_pos) = range_part.find('-') {
-                let start_str = &range_part[..dash_pos];
-                let end_str = &range_part[dash_pos + 1..];
-
-                let start: i64 = start_str
-                    .parse()
-                    .map_err(|_| FastaError::InvalidRegion(region.to_string()))?;
-                let end: i64 = end_str
-                    .parse()
-                    .map_err(|_| FastaError::InvalidRegion(region.to_string()))?;
-
-                // Convert from 1-based to 0-based coordinates
-                self.fetch_seq(seqname, start - 1, end)
-            } else {
-                Err(FastaError::InvalidRegion(region.to_string()))
-            }
-        } else {
-            // No colon, assume it's just a sequence name
-            self.fetch_seq_all(region)
+    /// A new `FastaIndex` instance, an [`FastaError::InvalidPath`] error if
+    /// the FASTA cannot be read, an [`FastaError::Io`] error if the index
+    /// cannot be read, or an [`FastaError::IndexLoadError`] if it cannot be parsed
+    pub fn new_with_index(
+        fasta_path: &str,
+        fai_path: &str,
+        format: FastaFormat,
+    ) -> FastaResult<Self> {
+        if std::fs::File::open(fasta_path).is_err() {
+            return Err(FastaError::InvalidPath(fasta_path.to_string()));
         }
+        require_file(fai_path, &format!("index file for {}", fasta_path))?;
+
+        let c_path = CString::new(fasta_path)
+            .map_err(|_| FastaError::InvalidPath(fasta_path.to_string()))?;
+        let c_fai_path =
+            CString::new(fai_path).map_err(|_| FastaError::InvalidPath(fai_path.to_string()))?;
+        let meta =
+            unsafe { faidx_meta_load3(c_path.as_ptr(), c_fai_path.as_ptr(), format.into(), 0) };
+
+        if meta.is_null() {
+            return Err(load_failure(
+                format!("{} for {}", fai_path, fasta_path),
+                "failed to load index",
+            ));
+        }
+
+        Self::from_meta(meta, format)
+    }
+
+    /// Wrap freshly loaded metadata, checking it against the FASTA
+    fn from_meta(meta: *mut faidx_meta_t, format: FastaFormat) -> FastaResult<Self> {
+        let index = FastaIndex {
+            meta,
+            format,
+            selected: None,
+            digests: Arc::new(OnceLock::new()),
+            total_length: Arc::new(OnceLock::new()),
+            read_buffer_size: None,
+            mmap: None,
+            pending_fetches: Arc::new(AtomicUsize::new(0)),
+        };
+        if index.is_compressed() {
+            use std::os::unix::ffi::OsStrExt;
+            let path = unsafe { CStr::from_ptr((*meta).fasta_path) };
+            let fs_path = std::path::Path::new(std::ffi::OsStr::from_bytes(path.to_bytes()));
+            if !has_bgzf_header(fs_path) {
+                return Err(FastaError::NotBgzf(path.to_string_lossy().into_owned()));
+            }
+        }
+        Ok(index)
+    }
+
+    /// Open a FASTA index from an existing `.fai`, never creating one
+    ///
+    /// Suitable for read-only reference volumes where the index files are
+    /// provisioned separately. Unlike [`new`](Self::new), a missing FASTA or
+    /// `.fai` is reported explicitly before anything is loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the FASTA/FASTQ file
+    /// * `format` - Format of the file (FASTA or FASTQ)
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaIndex` instance or an error if the file or its index is
+    /// missing or cannot be loaded
+    pub fn open(path: &str, format: FastaFormat) -> FastaResult<Self> {
+        require_file(path, "FASTA file")?;
+        let fai_path = format!("{}.fai", path);
+        require_file(
+            &fai_path,
+            &format!("index file (create with: samtools faidx {})", path),
+        )?;
+
+        Self::new(path, format)
+    }
+
+    /// Start configuring a FASTA index with non-default loading or reader options
+    ///
+    /// See [`FastaIndexBuilder`] for the available options.
+    pub fn builder() -> FastaIndexBuilder {
+        FastaIndexBuilder {
+            format: FastaFormat::Fasta,
+            read_buffer_size: None,
+            create: IndexCreation::Never,
+            index_path: None,
+            mmap: false,
+            validate_offsets: false,
+        }
+    }
+
+    /// Create a FASTA index restricted to the sequences accepted by `keep`
+    ///
+    /// The full `.fai` is loaded, but only the accepted sequences are visible
+    /// through the index: rejected names are not listed, have no length, and
+    /// fetching them from a reader fails with [`FastaError::SequenceNotFound`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the FASTA/FASTQ file
+    /// * `format` - Format of the file (FASTA or FASTQ)
+    /// * `keep` - Predicate on sequence names selecting the sequences to keep
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaIndex` instance or an error if the file cannot be loaded
+    pub fn new_filtered(
+        path: &str,
+        format: FastaFormat,
+        keep: impl Fn(&str) -> bool,
+    ) -> FastaResult<Self> {
+        let mut index = Self::new(path, format)?;
+        let selected = (0..index.num_sequences())
+            .filter(|&i| index.sequence_name(i).is_some_and(|name| keep(&name)))
+            .collect();
+        index.selected = Some(Arc::new(selected));
+        Ok(index)
+    }
+
+    /// Cross-check `.fai` offsets against the size of the (uncompressed) FASTA data
+    ///
+    /// Catches indexes that are out of sync with their FASTA, where fetching an
+    /// entry whose data lies past EOF would otherwise return garbage.
+    fn validate_offsets(&self) -> FastaResult<()> {
+        let data_size = unsafe { fasta_data_size(self.meta) }?;
+
+        for name in self.sequence_names() {
+            let Some(entry) = self.entry(&name) else {
+                continue;
+            };
+
+            let data_end = entry_data_end(&entry);
+            if data_end > data_size {
+                return Err(FastaError::InconsistentIndex(format!(
+                    "{}: sequence data ends at offset {} but file has {} bytes",
+                    name, data_end, data_size
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the number of sequences in the index
+    pub fn num_sequences(&self) -> usize {
+        match &self.selected {
+            Some(selected) => selected.len(),
+            None => unsafe { faidx_meta_nseq(self.meta) as usize },
+        }
+    }
+
+    /// Get the name of the sequence at the given index
+    pub fn sequence_name(&self, index: usize) -> Option<String> {
+        let id = match &self.selected {
+            Some(selected) => *selected.get(index)?,
+            None => index,
+        };
+        let name_ptr = unsafe { faidx_meta_iseq(self.meta, id as c_int) };
+        if name_ptr.is_null() {
+            None
+        } else {
+            let c_str = unsafe { CStr::from_ptr(name_ptr) };
+            Some(c_str.to_string_lossy().to_string())
+        }
+    }
+
+    /// Get the length of the specified sequence
+    pub fn sequence_length(&self, name: &str) -> Option<i64> {
+        if self.selected.is_some() && self.rank(name).is_none() {
+            return None;
+        }
+        let c_name = CString::new(name).ok()?;
+        let length = unsafe { faidx_meta_seq_len(self.meta, c_name.as_ptr()) };
+        if length < 0 {
+            None
+        } else {
+            Some(length)
+        }
+    }
+
+    /// Get the number of bases per line of the specified sequence
+    ///
+    /// This is the `LINEBASES` column of the `.fai`. Together with
+    /// [`line_width`](Self::line_width) it maps a base position to a byte
+    /// offset in the uncompressed file.
+    pub fn line_bases(&self, name: &str) -> Option<i64> {
+        if self.selected.is_some() && self.rank(name).is_none() {
+            return None;
+        }
+        self.entry(name).map(|entry| entry.line_blen as i64)
+    }
+
+    /// Get the number of bytes per line of the specified sequence
+    ///
+    /// This is the `LINEWIDTH` column of the `.fai`, which includes the
+    /// line terminator (one byte longer than [`line_bases`](Self::line_bases)
+    /// for `\n`, two for `\r\n`).
+    pub fn line_width(&self, name: &str) -> Option<i64> {
+        if self.selected.is_some() && self.rank(name).is_none() {
+            return None;
+        }
+        self.entry(name).map(|entry| entry.line_len as i64)
+    }
+
+    /// Check if the index contains the specified sequence
+    pub fn has_sequence(&self, name: &str) -> bool {
+        if self.selected.is_some() {
+            return self.rank(name).is_some();
+        }
+        let c_name = CString::new(name).unwrap_or_else(|_| CString::new("").unwrap());
+        unsafe { faidx_meta_has_seq(self.meta, c_name.as_ptr()) != 0 }
+    }
+
+    /// Check that a region lies entirely within a sequence
+    ///
+    /// This is a metadata lookup only, for filtering intervals before
+    /// fetching them.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the sequence exists and `0 <= start <= end <= length`
+    pub fn region_in_bounds(&self, name: &str, start: i64, end: i64) -> bool {
+        self.sequence_length(name)
+            .is_some_and(|length| 0 <= start && start <= end && end <= length)
+    }
+
+    /// Parse a region string against the sequence names in this index
+    ///
+    /// Like [`Region::parse`], except that a string naming a sequence is never
+    /// split, so names containing colons (e.g. "HLA:A*01:01") parse correctly.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - Region string in format "seqname:start-end"
+    ///
+    /// # Returns
+    ///
+    /// The parsed region or an `InvalidRegion` error
+    pub fn parse_region(&self, region: &str) -> FastaResult<Region> {
+        Region::parse_with(region, |name| self.has_sequence(name))
+    }
+
+    /// Resolve a sequence name, tolerating a missing or extra `chr` prefix
+    ///
+    /// Tries `name` as given, then with `chr` prepended, then with a leading
+    /// `chr` removed, and returns the first that is in the index. This is a
+    /// convenience for matching annotations against references with the
+    /// other naming convention (`chr1` vs `1`); all other lookups match names
+    /// exactly.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Sequence name to resolve
+    ///
+    /// # Returns
+    ///
+    /// The name as it appears in the index, or `None` if no variant is present
+    pub fn resolve_name(&self, name: &str) -> Option<String> {
+        if self.has_sequence(name) {
+            return Some(name.to_string());
+        }
+        let prefixed = format!("chr{}", name);
+        if self.has_sequence(&prefixed) {
+            return Some(prefixed);
+        }
+        name.strip_prefix("chr")
+            .filter(|stripped| self.has_sequence(stripped))
+            .map(str::to_string)
+    }
+
+    /// Get the position of the specified sequence in index order
+    ///
+    /// This is the order of sequences in the `.fai` (and thus the reference),
+    /// suitable for sorting records by contig order rather than lexicographically
+    /// or for formats that refer to sequences by their order, such as BAM
+    /// headers. It is the inverse of [`sequence_name`](Self::sequence_name) and
+    /// uses the `.fai` hash rather than scanning the names. For a filtered
+    /// index, the rank counts only the visible sequences.
+    pub fn rank(&self, name: &str) -> Option<usize> {
+        let id = self.entry(name)?.id as usize;
+        match &self.selected {
+            Some(selected) => selected.binary_search(&id).ok(),
+            None => Some(id),
+        }
+    }
+
+    /// Find the sequence whose content has the given MD5 digest
+    ///
+    /// Digests are computed as in refget and the SAM `M5` tag: over the
+    /// uppercased sequence, ignoring whitespace. All digests are computed on
+    /// the first call, which reads every sequence, and cached for later calls.
+    /// If several sequences share a digest, the first in index order is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `md5` - Hex-encoded MD5 digest (case-insensitive)
+    ///
+    /// # Returns
+    ///
+    /// The matching sequence name, if any, or an error if a sequence cannot be read
+    pub fn find_by_md5(&self, md5: &str) -> FastaResult<Option<String>> {
+        if self.digests.get().is_none() {
+            let reader = FastaReader::new(self)?;
+            let mut digests = HashMap::new();
+            let mut normalized = Vec::new();
+            for name in self.sequence_names() {
+                let mut hasher = Md5::new();
+                reader.for_each_block(&name, |block| {
+                    normalized.clear();
+                    normalized.extend(
+                        block
+                            .iter()
+                            .filter(|b| b.is_ascii_graphic())
+                            .map(u8::to_ascii_uppercase),
+                    );
+                    hasher.update(&normalized);
+                    Ok(())
+                })?;
+                digests.entry(hasher.hex_digest()).or_insert(name);
+            }
+            // Another thread may have filled the cache meanwhile; both results agree
+            let _ = self.digests.set(digests);
+        }
+
+        let digests = self.digests.get().expect("digest cache initialized");
+        Ok(digests.get(&md5.to_ascii_lowercase()).cloned())
+    }
+
+    /// Count sequences by length into bins delimited by `bins`
+    ///
+    /// With `n` ascending boundaries, `n + 1` counts are returned: the first
+    /// counts sequences shorter than `bins[0]`, entry `i` counts lengths in
+    /// `[bins[i - 1], bins[i])`, and the last counts lengths of at least
+    /// `bins[n - 1]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bins` - Bin boundaries in ascending order
+    ///
+    /// # Returns
+    ///
+    /// The number of sequences in each bin
+    pub fn length_histogram(&self, bins: &[i64]) -> Vec<usize> {
+        let mut counts = vec![0; bins.len() + 1];
+        for name in self.sequence_names() {
+            if let Some(length) = self.sequence_length(&name) {
+                counts[bins.partition_point(|&boundary| boundary <= length)] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Get the BGZF virtual offset range covering a sequence's data
+    ///
+    /// Virtual offsets combine the compressed offset of a BGZF block (upper 48
+    /// bits) with an offset into its uncompressed data (lower 16 bits). The
+    /// range starts at the first base and ends just past the last base, and is
+    /// resolved using the `.gzi` index.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    ///
+    /// # Returns
+    ///
+    /// The `(start, end)` virtual offsets, or an error if the sequence is
+    /// unknown or the file is not BGZF-compressed with a `.gzi` index
+    pub fn compressed_range(&self, seqname: &str) -> FastaResult<(u64, u64)> {
+        if !self.has_sequence(seqname) {
+            return Err(FastaError::SequenceNotFound(seqname.to_string()));
+        }
+        let entry = self
+            .entry(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        let blocks = unsafe {
+            let gzi = (*self.meta).gzi_index;
+            if (*self.meta).is_bgzf == 0 || gzi.is_null() {
+                let path = CStr::from_ptr((*self.meta).gzi_path).to_string_lossy();
+                return Err(FastaError::IndexLoadError(format!(
+                    "{}: BGZF index not loaded",
+                    path
+                )));
+            }
+            if (*gzi).n_entries <= 0 {
+                &[]
+            } else {
+                std::slice::from_raw_parts((*gzi).entries, (*gzi).n_entries as usize)
+            }
+        };
+
+        // The first block starts at (0, 0) and is implicit in the .gzi
+        let virtual_offset = |uoffset: u64| {
+            let idx = blocks.partition_point(|block| block.uncompressed_offset <= uoffset);
+            let (coffset, block_start) = match idx {
+                0 => (0, 0),
+                _ => (
+                    blocks[idx - 1].compressed_offset,
+                    blocks[idx - 1].uncompressed_offset,
+                ),
+            };
+            (coffset << 16) | (uoffset - block_start)
+        };
+
+        Ok((
+            virtual_offset(entry.seq_offset),
+            virtual_offset(entry_data_end(&entry)),
+        ))
+    }
+
+    /// Look up the raw `.fai` entry for a sequence, ignoring any filter
+    fn entry(&self, name: &str) -> Option<faidx1_t> {
+        let c_name = CString::new(name).ok()?;
+        let entry = unsafe { faidx_meta_get_entry(self.meta, c_name.as_ptr()) };
+        if entry.is_null() {
+            None
+        } else {
+            Some(unsafe { *entry })
+        }
+    }
+
+    /// Get all sequence names in the index
+    pub fn sequence_names(&self) -> Vec<String> {
+        self.iter_names().collect()
+    }
+
+    /// Get the name and length of every sequence in index order
+    ///
+    /// Collects the metadata in one pass over index positions, instead of
+    /// separate [`sequence_name`](Self::sequence_name) and
+    /// [`sequence_length`](Self::sequence_length) calls with name lookups.
+    pub fn records(&self) -> Vec<SequenceRecord> {
+        (0..self.num_sequences())
+            .filter_map(|i| {
+                Some(SequenceRecord {
+                    name: self.sequence_name(i)?,
+                    length: self.length_at(i)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Extract the sequence names and lengths as standalone metadata
+    ///
+    /// Unlike the index itself, the result holds no handle to the underlying
+    /// C structures, and with the `serde` feature it can be serialized.
+    pub fn metadata(&self) -> IndexMetadata {
+        IndexMetadata {
+            sequences: self.records(),
+        }
+    }
+
+    /// Get a map from every sequence name to its length
+    ///
+    /// Built in one pass over index positions, without per-name lookups.
+    /// Handy for chrom.sizes files and for validating intervals against
+    /// contig bounds.
+    pub fn length_map(&self) -> HashMap<String, i64> {
+        self.records()
+            .into_iter()
+            .map(|record| (record.name, record.length))
+            .collect()
+    }
+
+    /// Write a two-column genome file (`name<TAB>length`), as used by bedtools
+    ///
+    /// One line is written per sequence, in index order.
+    ///
+    /// # Arguments
+    ///
+    /// * `w` - Destination for the genome file
+    pub fn write_sizes<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        for record in self.records() {
+            writeln!(w, "{}\t{}", record.name, record.length)?;
+        }
+        Ok(())
+    }
+
+    /// Write a two-column genome file to `path`, replacing any existing file
+    ///
+    /// See [`write_sizes`](Self::write_sizes).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the genome file to create
+    pub fn write_sizes_to_path<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+        self.write_sizes(&mut out)?;
+        std::io::Write::flush(&mut out)
+    }
+
+    /// Get the format the index was opened with
+    pub fn format(&self) -> FastaFormat {
+        self.format
+    }
+
+    /// Check if the index was opened as FASTQ, so quality scores can be fetched
+    pub fn is_fastq(&self) -> bool {
+        self.format == FastaFormat::Fastq
+    }
+
+    /// Check if the file is gzip-compressed (plain gzip or bgzip)
+    pub fn is_compressed(&self) -> bool {
+        unsafe { (*self.meta).is_bgzf != 0 }
+    }
+
+    /// Check if the file is compressed with bgzip
+    ///
+    /// Random access into a compressed file needs BGZF blocks, so files
+    /// compressed with plain `gzip` are rejected when loading with
+    /// [`FastaError::NotBgzf`]; a loaded compressed file is always BGZF.
+    pub fn is_bgzf(&self) -> bool {
+        self.is_compressed()
+    }
+
+    /// Get the path of the `.gzi` index, if one was loaded for a BGZF file
+    ///
+    /// The `.gzi` (created by `bgzip -i` or `samtools faidx`) maps compressed
+    /// to uncompressed offsets; without it, fetches must scan BGZF blocks from
+    /// the start of the file.
+    pub fn gzi_path(&self) -> Option<std::path::PathBuf> {
+        unsafe {
+            if (*self.meta).gzi_index.is_null() {
+                return None;
+            }
+            let path = CStr::from_ptr((*self.meta).gzi_path);
+            Some(path.to_string_lossy().into_owned().into())
+        }
+    }
+
+    /// Get the length of the sequence at the given index
+    fn length_at(&self, index: usize) -> Option<i64> {
+        let id = match &self.selected {
+            Some(selected) => *selected.get(index)?,
+            None => index,
+        };
+        let length = unsafe { faidx_meta_iseq_len(self.meta, id as c_int) };
+        (length >= 0).then_some(length)
+    }
+
+    /// Get the total length of all sequences in the index
+    ///
+    /// The sum is computed once and cached, and shared with clones of the index.
+    pub fn total_length(&self) -> i64 {
+        *self
+            .total_length
+            .get_or_init(|| self.records().iter().map(|record| record.length).sum())
+    }
+
+    /// Iterate over the sequence names in index order
+    ///
+    /// Names are looked up lazily as the iterator advances.
+    pub fn iter_names(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.num_sequences()).filter_map(move |i| self.sequence_name(i))
+    }
+
+    /// Tile every sequence in the index into non-overlapping chunks
+    ///
+    /// Yields `(seqname, start, end)` tuples (0-based, half-open) in index
+    /// order. Each sequence is tiled independently, so chunks never span a
+    /// sequence boundary and the final chunk of a sequence may be shorter
+    /// than `chunk_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is not positive.
+    pub fn genome_chunks(&self, chunk_size: i64) -> impl Iterator<Item = (String, i64, i64)> + '_ {
+        assert!(chunk_size > 0, "chunk size must be positive");
+
+        self.sequence_names().into_iter().flat_map(move |name| {
+            let length = self.sequence_length(&name).unwrap_or(0);
+            (0..length)
+                .step_by(chunk_size as usize)
+                .map(move |start| (name.clone(), start, (start + chunk_size).min(length)))
+        })
+    }
+
+    /// Map a range of the concatenated coordinate space back to sequences
+    ///
+    /// The global coordinate space lays out every sequence end to end in index
+    /// order, so sequence `i` occupies `[offset_i, offset_i + length_i)` where
+    /// `offset_i` is the total length of the preceding sequences.
+    ///
+    /// # Arguments
+    ///
+    /// * `global_start` - Start of the global range (0-based, inclusive)
+    /// * `global_end` - End of the global range (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// `(seqname, start, end)` for every sequence overlapping the range, with
+    /// local 0-based, half-open coordinates, in index order
+    pub fn sequences_in_global_range(
+        &self,
+        global_start: i64,
+        global_end: i64,
+    ) -> Vec<(String, i64, i64)> {
+        let mut overlaps = Vec::new();
+        let mut offset = 0;
+        for name in self.sequence_names() {
+            if offset >= global_end {
+                break;
+            }
+            let length = self.sequence_length(&name).unwrap_or(0);
+            let start = (global_start - offset).max(0);
+            let end = (global_end - offset).min(length);
+            if start < end {
+                overlaps.push((name, start, end));
+            }
+            offset += length;
+        }
+        overlaps
+    }
+}
+
+impl FastaIndex {
+    /// Extract the sequence of every interval in a BED file using multiple threads
+    ///
+    /// Intervals are split into contiguous batches, one per thread, and each
+    /// thread fetches its batch with its own reader. Intervals on the `-`
+    /// strand are reverse-complemented. Results are returned in file order.
+    ///
+    /// # Arguments
+    ///
+    /// * `bed_path` - Path to the BED file
+    /// * `threads` - Number of worker threads (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// Each BED record paired with its sequence, or the first error encountered
+    pub fn fetch_bed_par(
+        &self,
+        bed_path: &str,
+        threads: usize,
+    ) -> FastaResult<Vec<(BedRecord, String)>> {
+        if threads == 0 {
+            return Err(FastaError::InvalidParameter(
+                "thread count must be at least 1".to_string(),
+            ));
+        }
+
+        let records = read_bed(bed_path)?;
+        if records.is_empty() {
+            return Ok(Vec::new());
+        }
+        let batch_size = (records.len() + threads - 1) / threads;
+
+        let batches: Vec<FastaResult<Vec<String>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = records
+                .chunks(batch_size)
+                .map(|batch| {
+                    scope.spawn(move || {
+                        let reader = FastaReader::new(self)?;
+                        batch
+                            .iter()
+                            .map(|record| {
+                                reader.fetch_stranded(
+                                    &record.chrom,
+                                    record.start,
+                                    record.end,
+                                    record.read_strand(),
+                                )
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("BED worker thread panicked"))
+                .collect()
+        });
+
+        let mut sequences = Vec::with_capacity(records.len());
+        for batch in batches {
+            sequences.extend(batch?);
+        }
+        Ok(records.into_iter().zip(sequences).collect())
+    }
+}
+
+/// Builder for a [`FastaIndex`] with custom loading and reader options
+///
+/// Created with [`FastaIndex::builder`]; the index is loaded by [`open`](Self::open).
+#[derive(Debug, Clone)]
+pub struct FastaIndexBuilder {
+    format: FastaFormat,
+    read_buffer_size: Option<usize>,
+    create: IndexCreation,
+    index_path: Option<std::path::PathBuf>,
+    mmap: bool,
+    validate_offsets: bool,
+}
+
+impl FastaIndexBuilder {
+    /// Set the format of the file (FASTA by default)
+    pub fn format(mut self, format: FastaFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the size of the file read buffer used by readers of this index
+    ///
+    /// By default readers use the platform buffer sizes: 8 KiB for zlib on
+    /// compressed files and `BUFSIZ` (8 KiB with glibc) for stdio on plain
+    /// files. A larger buffer means fewer, larger reads from the file, which
+    /// speeds up sequential scans of whole sequences on network or spinning
+    /// disk storage, at the cost of `bytes` of memory per reader (twice that
+    /// for compressed files, which also buffer decompressed output). Small
+    /// random fetches gain nothing from a larger buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Buffer size in bytes
+    pub fn read_buffer_size(mut self, bytes: usize) -> Self {
+        self.read_buffer_size = Some(bytes);
+        self
+    }
+
+    /// Set when the `.fai` is built from the FASTA ([`IndexCreation::Never`] by default)
+    ///
+    /// With [`IndexCreation::Never`] the index must already exist, which
+    /// suits read-only reference volumes, and [`IndexCreation::Always`]
+    /// replaces an index left behind by an older version of the FASTA. Only
+    /// uncompressed FASTA files can be indexed here; use `samtools faidx`
+    /// for FASTQ or bgzip-compressed files.
+    pub fn create(mut self, create: IndexCreation) -> Self {
+        self.create = create;
+        self
+    }
+
+    /// Set the location of the `.fai` (`<path>.fai` when `None`)
+    pub fn index_path(mut self, index_path: Option<std::path::PathBuf>) -> Self {
+        self.index_path = index_path;
+        self
+    }
+
+    /// Fetch sequences from a memory map of the file (off by default)
+    ///
+    /// Readers resolve base offsets directly against the mapped file instead
+    /// of seeking and reading through a file handle, which saves two system
+    /// calls per fetch for workloads of many small random reads. Only
+    /// uncompressed files can be mapped; for gzip or bgzip files the option
+    /// is ignored and readers use the regular read path. Quality strings are
+    /// always read through the file handle.
+    pub fn mmap(mut self, mmap: bool) -> Self {
+        self.mmap = mmap;
+        self
+    }
+
+    /// Check the `.fai` against the FASTA when loading (off by default)
+    ///
+    /// Every entry's data must end within the (uncompressed) file, otherwise
+    /// loading fails with [`FastaError::InconsistentIndex`]; this catches an
+    /// index left behind after the FASTA was replaced or truncated. For
+    /// bgzip files the BGZF block headers after the last `.gzi` entry are
+    /// walked, which without a `.gzi` means reading a header per 64 KiB block
+    /// of the whole file.
+    pub fn validate_offsets(mut self, validate: bool) -> Self {
+        self.validate_offsets = validate;
+        self
+    }
+
+    /// Load the index for a FASTA/FASTQ file with the configured options
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the FASTA/FASTQ file
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaIndex` instance or an error if the file cannot be loaded
+    /// or an option is invalid
+    pub fn open(self, path: &str) -> FastaResult<FastaIndex> {
+        if let Some(bytes) = self.read_buffer_size {
+            if bytes == 0 || bytes > u32::MAX as usize {
+                return Err(FastaError::InvalidParameter(format!(
+                    "read buffer size must be between 1 and {} bytes, got {}",
+                    u32::MAX,
+                    bytes
+                )));
+            }
+        }
+
+        let fai_path = match &self.index_path {
+            Some(fai_path) => fai_path.to_string_lossy().into_owned(),
+            None => format!("{}.fai", path),
+        };
+        let c_path = CString::new(path).map_err(|_| FastaError::InvalidPath(path.to_string()))?;
+        let c_fai_path = CString::new(fai_path.as_str())
+            .map_err(|_| FastaError::InvalidPath(fai_path.clone()))?;
+
+        let create = match self.create {
+            IndexCreation::Never => false,
+            IndexCreation::IfMissing => !std::path::Path::new(&fai_path).is_file(),
+            IndexCreation::Always => true,
+        };
+        let mut flags = 0;
+        if create {
+            // The C indexer only understands plain FASTA text
+            if self.format == FastaFormat::Fastq || is_gzip(path) {
+                return Err(FastaError::IndexLoadError(format!(
+                    "{}: cannot create an index for FASTQ or compressed files. \
+                    Create index with: samtools faidx {}",
+                    path, path
+                )));
+            }
+            // The C loader only builds an index it cannot load
+            if self.create == IndexCreation::Always {
+                match std::fs::remove_file(&fai_path) {
+                    Err(source) if source.kind() != std::io::ErrorKind::NotFound => {
+                        return Err(FastaError::Io {
+                            context: fai_path,
+                            source,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            flags = FAI_CREATE as c_int;
+        }
+
+        let meta = unsafe {
+            faidx_meta_load3(
+                c_path.as_ptr(),
+                c_fai_path.as_ptr(),
+                self.format.into(),
+                flags,
+            )
+        };
+        if meta.is_null() {
+            return Err(load_failure(
+                format!("{} for {}", fai_path, path),
+                "failed to load index",
+            ));
+        }
+
+        let mut index = FastaIndex::from_meta(meta, self.format)?;
+        if self.validate_offsets {
+            index.validate_offsets()?;
+        }
+        index.read_buffer_size = self.read_buffer_size;
+        if self.mmap && !is_gzip(path) {
+            let map = FastaMmap::map(path).map_err(|source| FastaError::Io {
+                context: path.to_string(),
+                source,
+            })?;
+            index.mmap = Some(Arc::new(map));
+        }
+        Ok(index)
+    }
+}
+
+/// Whether the file starts with a BGZF block: a gzip header with a "BC" extra subfield
+fn has_bgzf_header(path: &std::path::Path) -> bool {
+    let mut header = [0u8; 14];
+    std::fs::File::open(path)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut header))
+        .map(|_| header[..2] == [0x1f, 0x8b] && header[3] & 0x04 != 0 && header[12..] == *b"BC")
+        .unwrap_or(false)
+}
+
+/// Whether the file starts with the gzip magic bytes (which BGZF shares)
+fn is_gzip(path: &str) -> bool {
+    let mut magic = [0u8; 2];
+    std::fs::File::open(path)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut magic))
+        .map(|_| magic == [0x1f, 0x8b])
+        .unwrap_or(false)
+}
+
+impl Clone for FastaIndex {
+    fn clone(&self) -> Self {
+        let meta = unsafe { faidx_meta_ref(self.meta) };
+        FastaIndex {
+            meta,
+            format: self.format,
+            selected: self.selected.clone(),
+            digests: Arc::clone(&self.digests),
+            total_length: Arc::clone(&self.total_length),
+            read_buffer_size: self.read_buffer_size,
+            mmap: self.mmap.clone(),
+            pending_fetches: Arc::clone(&self.pending_fetches),
+        }
+    }
+}
+
+impl Drop for FastaIndex {
+    fn drop(&mut self) {
+        unsafe {
+            faidx_meta_destroy(self.meta);
+        }
+    }
+}
+
+unsafe impl Send for FastaIndex {}
+unsafe impl Sync for FastaIndex {}
+
+/// Read-only memory map of a whole file
+struct FastaMmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl FastaMmap {
+    fn map(path: &str) -> std::io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len == 0 {
+            // mmap rejects empty mappings
+            return Ok(FastaMmap {
+                ptr: std::ptr::null_mut(),
+                len: 0,
+            });
+        }
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(FastaMmap { ptr, len })
+    }
+
+    fn bytes(&self) -> &[u8] {
+        if self.ptr.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+        }
+    }
+}
+
+impl Drop for FastaMmap {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe {
+                libc::munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
+
+unsafe impl Send for FastaMmap {}
+unsafe impl Sync for FastaMmap {}
+
+/// Uncompressed offset just past the last base of a `.fai` entry
+fn entry_data_end(entry: &faidx1_t) -> u64 {
+    if entry.line_blen == 0 {
+        entry.seq_offset + entry.len
+    } else {
+        let line_blen = entry.line_blen as u64;
+        entry.seq_offset + (entry.len / line_blen) * entry.line_len as u64 + entry.len % line_blen
+    }
+}
+
+/// Size in bytes of the uncompressed FASTA data
+///
+/// # Safety
+///
+/// `meta` must point to a valid, loaded `faidx_meta_t`.
+unsafe fn fasta_data_size(meta: *const faidx_meta_t) -> FastaResult<u64> {
+    let path = CStr::from_ptr((*meta).fasta_path)
+        .to_string_lossy()
+        .into_owned();
+    let io_err = |source: std::io::Error| FastaError::Io {
+        context: path.clone(),
+        source,
+    };
+
+    if (*meta).is_bgzf == 0 {
+        return std::fs::metadata(&path).map(|m| m.len()).map_err(io_err);
+    }
+
+    // Resume from the last block recorded in the .gzi, if any
+    let gzi = (*meta).gzi_index;
+    let resume = if gzi.is_null() || (*gzi).n_entries <= 0 {
+        (0, 0)
+    } else {
+        let last = *(*gzi).entries.add((*gzi).n_entries as usize - 1);
+        (last.compressed_offset, last.uncompressed_offset)
+    };
+
+    let mut file = std::fs::File::open(&path).map_err(io_err)?;
+    let file_len = file.metadata().map_err(io_err)?.len();
+    bgzf_uncompressed_size(&mut file, file_len, resume)
+        .map_err(io_err)?
+        .map_err(|block| {
+            FastaError::InconsistentIndex(format!(
+                "{}: truncated or corrupt BGZF block at offset {}",
+                path, block
+            ))
+        })
+}
+
+/// Walk BGZF block headers from `(compressed, uncompressed)` offsets to EOF,
+/// summing the uncompressed block sizes
+///
+/// The inner result is `Err` with the block's compressed offset if a block
+/// is not BGZF or runs past the end of the file.
+fn bgzf_uncompressed_size(
+    file: &mut std::fs::File,
+    file_len: u64,
+    resume: (u64, u64),
+) -> std::io::Result<Result<u64, u64>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let (mut coffset, mut uoffset) = resume;
+
+    while coffset < file_len {
+        // gzip header with the BGZF "BC" extra subfield holding the block size
+        let mut header = [0u8; 18];
+        if coffset + header.len() as u64 > file_len {
+            return Ok(Err(coffset));
+        }
+        file.seek(SeekFrom::Start(coffset))?;
+        file.read_exact(&mut header)?;
+        if header[0] != 0x1f || header[1] != 0x8b || header[12] != b'B' || header[13] != b'C' {
+            return Ok(Err(coffset));
+        }
+        let block_size = u16::from_le_bytes([header[16], header[17]]) as u64 + 1;
+        if block_size < header.len() as u64 + 8 || coffset + block_size > file_len {
+            return Ok(Err(coffset));
+        }
+
+        // The gzip trailer ends with ISIZE, the uncompressed block length
+        let mut isize = [0u8; 4];
+        file.seek(SeekFrom::Start(coffset + block_size - 4))?;
+        file.read_exact(&mut isize)?;
+
+        uoffset += u32::from_le_bytes(isize) as u64;
+        coffset += block_size;
+    }
+
+    Ok(Ok(uoffset))
+}
+
+/// Maximum number of timeout-bounded fetches running at once per index
+///
+/// See [`FastaReader::with_timeout`].
+pub const MAX_PENDING_FETCHES: usize = 16;
+
+/// A slot in an index's count of running timeout helper threads
+struct PendingFetch(Arc<AtomicUsize>);
+
+impl PendingFetch {
+    /// Take a slot, or `None` if `MAX_PENDING_FETCHES` are already taken
+    fn acquire(pending: &Arc<AtomicUsize>) -> Option<Self> {
+        pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < MAX_PENDING_FETCHES).then_some(n + 1)
+            })
+            .ok()?;
+        Some(PendingFetch(Arc::clone(pending)))
+    }
+}
+
+impl Drop for PendingFetch {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// FASTA reader for accessing sequences
+///
+/// This structure provides thread-safe access to FASTA/FASTQ sequences using
+/// a shared index. Each reader maintains its own file handle but shares the
+/// index metadata.
+///
+/// A reader is `Send` but not `Sync`: every fetch seeks its file handle and,
+/// for compressed files, advances the zlib stream state, so two threads
+/// fetching through one reader would interleave seeks and reads. Give each
+/// thread its own reader (see [`try_clone`](Self::try_clone)), or share a
+/// [`SyncFastaReader`] when serialized access is fast enough.
+pub struct FastaReader {
+    reader: *mut faidx_reader_t,
+    _index: Arc<FastaIndex>, // Keep index alive
+    strict: bool,
+    timeout: Option<Duration>,
+}
+
+impl FastaReader {
+    /// Create a new FASTA reader from an index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Shared FASTA index
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaReader` instance or an error if the reader cannot be created
+    pub fn new(index: &FastaIndex) -> FastaResult<Self> {
+        Self::from_shared(Arc::new(index.clone()))
+    }
+
+    /// Create a reader holding an already shared index
+    fn from_shared(index: Arc<FastaIndex>) -> FastaResult<Self> {
+        let reader = unsafe { faidx_reader_create(index.meta) };
+
+        if reader.is_null() {
+            return Err(FastaError::ReaderCreationError);
+        }
+        if let Some(bytes) = index.read_buffer_size {
+            if unsafe { faidx_reader_set_buffer_size(reader, bytes) } != 0 {
+                unsafe { faidx_reader_destroy(reader) };
+                return Err(FastaError::ReaderCreationError);
+            }
+        }
+
+        Ok(FastaReader {
+            reader,
+            _index: index,
+            strict: false,
+            timeout: None,
+        })
+    }
+
+    /// Create another reader on the same index
+    ///
+    /// The new reader has its own file handle, so it can be moved to another
+    /// thread, and keeps this reader's validation and timeout settings.
+    ///
+    /// # Returns
+    ///
+    /// A new `FastaReader` instance or an error if the reader cannot be created
+    pub fn try_clone(&self) -> FastaResult<Self> {
+        let mut reader = Self::from_shared(Arc::clone(&self._index))?;
+        reader.strict = self.strict;
+        reader.timeout = self.timeout;
+        Ok(reader)
+    }
+
+    /// Enable or disable strict validation of fetched sequences
+    ///
+    /// With strict validation, every fetch checks that the returned sequence
+    /// contains no line terminators and, for regions within the sequence
+    /// bounds, has exactly `end - start` bases. Violations indicate an index
+    /// whose line layout does not match the FASTA and are reported as
+    /// [`FastaError::InconsistentIndex`].
+    pub fn with_strict_validation(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Bound the time spent waiting for each sequence or quality fetch
+    ///
+    /// With a timeout set, each fetch runs on a helper thread with its own
+    /// reader, and a fetch that does not complete in time fails with
+    /// [`FastaError::IoError`]. The abandoned helper finishes in the background.
+    /// This guards services against stalled storage, at the cost of opening a
+    /// file handle per fetch. At most [`MAX_PENDING_FETCHES`] helpers may be
+    /// running per index, across all its readers; once that many are stuck,
+    /// further fetches fail immediately instead of piling up threads and
+    /// file handles.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Get the length of a sequence in the reader's index
+    ///
+    /// See [`FastaIndex::sequence_length`].
+    pub fn sequence_length(&self, name: &str) -> Option<i64> {
+        self._index.sequence_length(name)
+    }
+
+    /// Get the names of all sequences in the reader's index, in index order
+    ///
+    /// See [`FastaIndex::sequence_names`].
+    pub fn sequence_names(&self) -> Vec<String> {
+        self._index.sequence_names()
+    }
+
+    /// Run `fetch` against a fresh reader on a helper thread, waiting at most `timeout`
+    fn fetch_with_timeout<T: Send + 'static>(
+        &self,
+        timeout: Duration,
+        fetch: impl FnOnce(&FastaReader) -> FastaResult<T> + Send + 'static,
+    ) -> FastaResult<T> {
+        let pending = PendingFetch::acquire(&self._index.pending_fetches).ok_or_else(|| {
+            FastaError::IoError(format!(
+                "{} timed-out fetches still pending",
+                MAX_PENDING_FETCHES
+            ))
+        })?;
+        let index = Arc::clone(&self._index);
+        let strict = self.strict;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        // `None` if the timeout is too large to represent, i.e. never expires
+        let deadline = std::time::Instant::now().checked_add(timeout);
+
+        std::thread::spawn(move || {
+            // Released when the helper finishes, even if it panics
+            let _pending = pending;
+            let result = FastaReader::from_shared(index)
+                .and_then(|reader| fetch(&reader.with_strict_validation(strict)));
+            // The caller may have given up waiting
+            let _ = sender.send(result);
+        });
+
+        match receiver.recv_timeout(timeout) {
+            // A result that raced in after the deadline still counts as late
+            Ok(result) if deadline.map_or(true, |d| std::time::Instant::now() < d) => result,
+            _ => Err(FastaError::IoError("fetch timed out".to_string())),
+        }
+    }
+
+    /// Fetch a sequence from the specified region
+    ///
+    /// Negative coordinates and `start > end` are rejected with
+    /// [`FastaError::InvalidCoordinates`] before reaching the C reader. An `end`
+    /// past the sequence length is truncated to the length.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_seq(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        let bytes = self.fetch_seq_bytes(seqname, start, end)?;
+        Ok(String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+    }
+
+    /// Fetch a sequence, returning `None` for a region with no bases
+    ///
+    /// A region that is empty, or lies entirely past the end of the sequence,
+    /// yields `Ok(None)` without building an error. Unknown sequence names and
+    /// negative or reversed coordinates are still errors, as with
+    /// [`fetch_seq`](Self::fetch_seq).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string, `None` if the region holds no bases, or an error
+    /// if the sequence cannot be fetched
+    pub fn try_fetch_seq(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<Option<String>> {
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+        if start < 0 || start > end {
+            return Err(FastaError::InvalidCoordinates {
+                seqname: seqname.to_string(),
+                start,
+                end,
+            });
+        }
+        if start >= end.min(length) {
+            return Ok(None);
+        }
+        self.fetch_seq(seqname, start, end).map(Some)
+    }
+
+    /// Fetch a sequence from a half-open range of positions
+    ///
+    /// Equivalent to [`fetch_seq`](Self::fetch_seq) with `range.start` and
+    /// `range.end`, so `reader.fetch_range("chr1", 1000..2000)` returns 1000 bases.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `range` - Positions to fetch (0-based, end exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_range(&self, seqname: &str, range: std::ops::Range<i64>) -> FastaResult<String> {
+        self.fetch_seq(seqname, range.start, range.end)
+    }
+
+    /// Fetch a sequence using unsigned coordinates
+    ///
+    /// Negative positions cannot be expressed. Positions beyond `i64::MAX`,
+    /// which the C interface cannot address, are rejected with
+    /// [`FastaError::InvalidParameter`]; otherwise this behaves exactly like
+    /// [`fetch_seq`](Self::fetch_seq).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_seq_u64(&self, seqname: &str, start: u64, end: u64) -> FastaResult<String> {
+        let to_i64 = |position: u64| {
+            i64::try_from(position).map_err(|_| {
+                FastaError::InvalidParameter(format!(
+                    "position {} of {} exceeds the largest supported coordinate",
+                    position, seqname
+                ))
+            })
+        };
+        self.fetch_seq(seqname, to_i64(start)?, to_i64(end)?)
+    }
+
+    /// Fetch a sequence using `usize` coordinates, as used for slice indexing
+    ///
+    /// See [`fetch_seq_u64`](Self::fetch_seq_u64).
+    pub fn fetch_seq_usize(&self, seqname: &str, start: usize, end: usize) -> FastaResult<String> {
+        self.fetch_seq_u64(seqname, start as u64, end as u64)
+    }
+
+    /// Copy a region out of the memory-mapped FASTA, with `end` already clamped
+    fn fetch_mapped(
+        &self,
+        map: &FastaMmap,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<SeqBuf> {
+        if start >= end {
+            return Err(FastaError::InvalidCoordinates {
+                seqname: seqname.to_string(),
+                start,
+                end,
+            });
+        }
+        let entry = self
+            ._index
+            .entry(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        // Allocated like the C reader's buffers, so `SeqBuf` frees both alike
+        let len = (end - start) as usize;
+        let ptr = unsafe { libc::malloc(len) } as *mut u8;
+        if ptr.is_null() {
+            return Err(FastaError::MemoryError);
+        }
+        let mut seq = SeqBuf { ptr, len };
+
+        let line_blen = entry.line_blen as u64;
+        let (mut pos, end) = (start as u64, end as u64);
+        let mut filled = 0;
+        while pos < end {
+            let (offset, run) = if line_blen == 0 {
+                (entry.seq_offset + pos, end - pos)
+            } else {
+                let column = pos % line_blen;
+                (
+                    entry.seq_offset + (pos / line_blen) * entry.line_len as u64 + column,
+                    (line_blen - column).min(end - pos),
+                )
+            };
+            let bases = map
+                .bytes()
+                .get(offset as usize..(offset + run) as usize)
+                .ok_or_else(|| {
+                    FastaError::InconsistentIndex(format!(
+                        "{}:{}-{}: indexed offset {} lies past the end of the file",
+                        seqname,
+                        start,
+                        end,
+                        offset + run
+                    ))
+                })?;
+            seq[filled..filled + run as usize].copy_from_slice(bases);
+            filled += run as usize;
+            pos += run;
+        }
+        Ok(seq)
+    }
+
+    /// Fetch a region into a C-allocated buffer
+    ///
+    /// Like [`fetch_seq_bytes`](Self::fetch_seq_bytes), but the bytes are not
+    /// copied out of the C buffer, which halves peak memory when fetching
+    /// whole chromosomes. The buffer is freed when the [`SeqBuf`] is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence buffer or an error if the sequence cannot be fetched
+    pub fn fetch_seq_buf(&self, seqname: &str, start: i64, end: i64) -> FastaResult<SeqBuf> {
+        let c_seqname =
+            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+        self.fetch_c_seq_buf(seqname, &c_seqname, start, end)
+    }
+
+    /// Fetch the raw bytes of a sequence from the specified region
+    ///
+    /// Like [`fetch_seq`](Self::fetch_seq), but skips UTF-8 validation. The
+    /// result holds exactly the number of bytes reported by the C reader, so
+    /// embedded null bytes do not truncate it.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence bytes or an error if the sequence cannot be fetched
+    pub fn fetch_seq_bytes(&self, seqname: &str, start: i64, end: i64) -> FastaResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.fetch_seq_into(seqname, start, end, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Fetch a sequence into a caller-provided buffer
+    ///
+    /// `buf` is cleared and the fetched bytes are appended, so one buffer can
+    /// be reused across many fetches without reallocating. On error `buf` is
+    /// left empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `buf` - Buffer receiving the sequence bytes
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written or an error if the sequence cannot be fetched
+    pub fn fetch_seq_into(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        buf: &mut Vec<u8>,
+    ) -> FastaResult<usize> {
+        let c_seqname =
+            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+        self.fetch_c_seq_into(seqname, &c_seqname, start, end, buf)
+    }
+
+    /// Fetch into `buf` with the C copy of the name already built
+    fn fetch_c_seq_into(
+        &self,
+        seqname: &str,
+        c_seqname: &CStr,
+        start: i64,
+        end: i64,
+        buf: &mut Vec<u8>,
+    ) -> FastaResult<usize> {
+        buf.clear();
+        let seq = self.fetch_c_seq_buf(seqname, c_seqname, start, end)?;
+        buf.extend_from_slice(&seq);
+        Ok(seq.len())
+    }
+
+    fn fetch_c_seq_buf(
+        &self,
+        seqname: &str,
+        c_seqname: &CStr,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<SeqBuf> {
+        if start < 0 || end < 0 || start > end {
+            return Err(FastaError::InvalidCoordinates {
+                seqname: seqname.to_string(),
+                start,
+                end,
+            });
+        }
+        if start == end {
+            return Ok(SeqBuf::empty());
+        }
+        if let Some(timeout) = self.timeout {
+            let seqname = seqname.to_string();
+            return self.fetch_with_timeout(timeout, move |reader| {
+                reader.fetch_seq_buf(&seqname, start, end)
+            });
+        }
+        // Unknown names are reported before the fetch, so a failed fetch of a
+        // known sequence can be attributed to the region
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        if let Some(map) = &self._index.mmap {
+            let seq = self.fetch_mapped(map, seqname, start, end.min(length))?;
+            if self.strict {
+                self.validate_fetched(seqname, start, end, &seq)?;
+            }
+            return Ok(seq);
+        }
+
+        let mut len: i64 = 0;
+        let seq_ptr = unsafe {
+            faidx_reader_fetch_seq(self.reader, c_seqname.as_ptr(), start, end, &mut len)
+        };
+
+        if seq_ptr.is_null() {
+            // The C reader clamps `end` to the sequence and fails if nothing is left
+            if start >= end.min(length) {
+                return Err(FastaError::InvalidCoordinates {
+                    seqname: seqname.to_string(),
+                    start,
+                    end,
+                });
+            }
+            return Err(FastaError::IoError(format!(
+                "failed to read {}:{}-{}",
+                seqname, start, end
+            )));
+        }
+
+        let seq = SeqBuf {
+            ptr: seq_ptr as *mut u8,
+            len: len.max(0) as usize,
+        };
+        if self.strict {
+            self.validate_fetched(seqname, start, end, &seq)?;
+        }
+        Ok(seq)
+    }
+
+    /// Fetch a batch of regions in one call
+    ///
+    /// Each region is a `(seqname, start, end)` tuple with 0-based, half-open
+    /// coordinates, fetched as with [`fetch_seq`](Self::fetch_seq). The C copy
+    /// of each distinct sequence name and the read buffer are reused across
+    /// the batch, which pays off for large interval sets such as BED files.
+    ///
+    /// # Arguments
+    ///
+    /// * `regions` - Regions to fetch
+    ///
+    /// # Returns
+    ///
+    /// One result per region, in input order
+    pub fn fetch_regions(&self, regions: &[(String, i64, i64)]) -> Vec<FastaResult<String>> {
+        let mut c_names: HashMap<&str, CString> = HashMap::new();
+        let mut buf = Vec::new();
+
+        regions
+            .iter()
+            .map(|(seqname, start, end)| {
+                if !c_names.contains_key(seqname.as_str()) {
+                    let c_seqname = CString::new(seqname.as_str())
+                        .map_err(|_| FastaError::SequenceNotFound(seqname.clone()))?;
+                    c_names.insert(seqname, c_seqname);
+                }
+                let c_seqname = &c_names[seqname.as_str()];
+                self.fetch_c_seq_into(seqname, c_seqname, *start, *end, &mut buf)?;
+                Ok(String::from_utf8_lossy(&buf).into_owned())
+            })
+            .collect()
+    }
+
+    /// Check a fetched sequence against the layout promised by the index
+    fn validate_fetched(&self, seqname: &str, start: i64, end: i64, seq: &[u8]) -> FastaResult<()> {
+        if seq.iter().any(|&b| b == b'\n' || b == b'\r') {
+            return Err(FastaError::InconsistentIndex(format!(
+                "{}:{}-{}: fetched sequence contains line terminators",
+                seqname, start, end
+            )));
+        }
+
+        let length = self._index.sequence_length(seqname).unwrap_or(0);
+        if start >= 0 && start < end && end <= length && seq.len() as i64 != end - start {
+            return Err(FastaError::InconsistentIndex(format!(
+                "{}:{}-{}: expected {} bases, fetched {}",
+                seqname,
+                start,
+                end,
+                end - start,
+                seq.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch a region, rejecting coordinates outside the sequence
+    ///
+    /// Unlike [`fetch_seq`](Self::fetch_seq), which silently truncates regions
+    /// running past the end, a region with `start < 0` or `end > length`
+    /// fails with [`FastaError::OutOfBounds`]. The error carries the sequence
+    /// length and the region clamped to the sequence, which the caller can
+    /// retry with.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the region is invalid or cannot be fetched
+    pub fn fetch_seq_checked(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        if start > end {
+            return Err(FastaError::InvalidCoordinates {
+                seqname: seqname.to_string(),
+                start,
+                end,
+            });
+        }
+        if start < 0 || end > length {
+            let (clamped_start, clamped_end) = (start.clamp(0, length), end.clamp(0, length));
+            return Err(FastaError::OutOfBounds {
+                seqname: seqname.to_string(),
+                start,
+                end,
+                length,
+                suggested: (clamped_start < clamped_end).then_some((clamped_start, clamped_end)),
+            });
+        }
+
+        self.fetch_seq(seqname, start, end)
+    }
+
+    /// Fetch a region clamped to the sequence, as `samtools faidx` does
+    ///
+    /// `start` is clamped to 0 and `end` to the sequence length, and whatever
+    /// bases remain are returned: `chr1:999999999-1000000000` on a shorter
+    /// sequence yields an empty string rather than an error, and a region
+    /// overlapping the end yields the available tail. A region that is empty
+    /// after clamping (including `start >= end`) also yields an empty string.
+    /// Unknown sequence names are still an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The available part of the region or an error if the sequence cannot be fetched
+    pub fn fetch_seq_clamped(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        self.fetch_seq_lenient(seqname, start, end)
+            .map(|(sequence, _)| sequence)
+    }
+
+    /// Fetch a region, clamping out-of-bounds coordinates to the sequence
+    ///
+    /// Coordinates are clamped to `[0, length]` before fetching. A region that
+    /// lies entirely outside the sequence yields an empty string.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string and whether the coordinates were clamped, or an
+    /// error if the sequence cannot be fetched
+    pub fn fetch_seq_lenient(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<(String, bool)> {
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        let clamped_start = start.clamp(0, length);
+        let clamped_end = end.clamp(0, length);
+        let clamped = (clamped_start, clamped_end) != (start, end);
+
+        if clamped_start >= clamped_end {
+            return Ok((String::new(), clamped));
+        }
+        let sequence = self.fetch_seq(seqname, clamped_start, clamped_end)?;
+        Ok((sequence, clamped))
+    }
+
+    /// Fetch a window of exactly `length` bases, padding past the sequence ends
+    ///
+    /// The window is placed relative to `center` according to `anchor`:
+    /// `Center` covers `[center - length / 2, center - length / 2 + length)`,
+    /// `Left` covers `[center, center + length)` and `Right` covers
+    /// `[center - length, center)`. Positions of the window outside the
+    /// sequence are filled with `pad`, so the real bases keep their place
+    /// within the window. Non-ASCII bytes in the FASTA are replaced by `N`,
+    /// keeping one byte per position.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `center` - Anchor position (0-based)
+    /// * `length` - Width of the window
+    /// * `pad` - Byte used for positions outside the sequence
+    /// * `anchor` - How the window is placed relative to `center`
+    ///
+    /// # Returns
+    ///
+    /// A string of exactly `length` bytes, or an error if the sequence cannot
+    /// be fetched or the window lies beyond the range of `i64`
+    pub fn fetch_fixed(
+        &self,
+        seqname: &str,
+        center: i64,
+        length: i64,
+        pad: u8,
+        anchor: Anchor,
+    ) -> FastaResult<String> {
+        if length < 0 {
+            return Err(FastaError::InvalidParameter(format!(
+                "window length must not be negative, got {}",
+                length
+            )));
+        }
+        if !pad.is_ascii() {
+            return Err(FastaError::InvalidParameter(format!(
+                "padding byte must be ASCII, got {:#04x}",
+                pad
+            )));
+        }
+
+        let out_of_range = || {
+            FastaError::InvalidParameter(format!(
+                "window of {} bases at {} is out of range",
+                length, center
+            ))
+        };
+        let start = match anchor {
+            Anchor::Center => center.checked_sub(length / 2),
+            Anchor::Left => Some(center),
+            Anchor::Right => center.checked_sub(length),
+        }
+        .ok_or_else(out_of_range)?;
+        let end = start.checked_add(length).ok_or_else(out_of_range)?;
+        let seq_length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+        let (fetch_start, fetch_end) = (start.clamp(0, seq_length), end.clamp(0, seq_length));
+
+        let mut window = Vec::with_capacity(length as usize);
+        if fetch_start < fetch_end {
+            // Bases missing on the left are those before position 0
+            window.resize((fetch_start - start) as usize, pad);
+            let bases = self.fetch_seq_bytes(seqname, fetch_start, fetch_end)?;
+            window.extend(bases.iter().map(|&b| if b.is_ascii() { b } else { b'N' }));
+        }
+        window.resize(length as usize, pad);
+        Ok(String::from_utf8(window).expect("window bytes are ASCII"))
+    }
+
+    /// Iterate over every sequence in the index as `(name, sequence)` pairs
+    ///
+    /// Sequences are fetched one at a time as the iterator advances, in index
+    /// order, so only the current sequence is held in memory. The iterator
+    /// borrows the reader and cannot outlive it.
+    pub fn iter_sequences(&self) -> impl Iterator<Item = FastaResult<(String, String)>> + '_ {
+        self._index.iter_names().map(move |name| {
+            let sequence = self.fetch_seq_all(&name)?;
+            Ok((name, sequence))
+        })
+    }
+
+    /// Iterate over windows of a sequence as `(start, sequence)` pairs
+    ///
+    /// Windows of `window` bases start at 0, `step`, `2 * step`, ... while
+    /// the start is within the sequence. `partial` decides whether windows
+    /// reaching past the end are truncated or skipped. Each window is fetched
+    /// as the iterator advances.
+    ///
+    /// The iterator is wrapped in a `FastaResult` so that an unknown sequence
+    /// or a bad `window` or `step` fails at the call, before any iteration;
+    /// only fetch failures are reported through the items.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `window` - Window size in bases (at least 1)
+    /// * `step` - Distance between window starts (at least 1)
+    /// * `partial` - Handling of windows running past the sequence end
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the windows, or an error if the sequence is unknown
+    /// or `window` or `step` is not positive
+    pub fn windows(
+        &self,
+        seqname: &str,
+        window: i64,
+        step: i64,
+        partial: PartialWindow,
+    ) -> FastaResult<impl Iterator<Item = FastaResult<(i64, String)>> + '_> {
+        if window < 1 || step < 1 {
+            return Err(FastaError::InvalidParameter(format!(
+                "window size and step must be at least 1, got {} and {}",
+                window, step
+            )));
+        }
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        // One past the last start yielded
+        let last_start = match partial {
+            PartialWindow::Truncate => length,
+            PartialWindow::Skip => (length - window + 1).max(0),
+        };
+        let seqname = seqname.to_string();
+        Ok((0..last_start).step_by(step as usize).map(move |start| {
+            let end = (start + window).min(length);
+            Ok((start, self.fetch_seq(&seqname, start, end)?))
+        }))
+    }
+
+    /// Fetch a sequence as a shared, reference-counted string
+    ///
+    /// The result can be cloned cheaply into multiple owners without copying
+    /// the sequence bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The shared sequence or an error if the sequence cannot be fetched
+    pub fn fetch_seq_shared(&self, seqname: &str, start: i64, end: i64) -> FastaResult<Arc<str>> {
+        self.fetch_seq(seqname, start, end).map(Arc::from)
+    }
+
+    /// Fetch the entire sequence
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    ///
+    /// # Returns
+    ///
+    /// The complete sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_seq_all(&self, seqname: &str) -> FastaResult<String> {
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+
+        self.fetch_seq(seqname, 0, length)
+    }
+
+    /// Fetch a region of a circular sequence, wrapping around the origin
+    ///
+    /// When `start > end`, or `end` runs past the sequence length, the region
+    /// continues from the beginning of the sequence (e.g. `chrM:16500-200`).
+    /// Regions that do not wrap are fetched exactly as with [`fetch_seq`](Self::fetch_seq).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive), possibly past the origin
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the region is invalid or cannot be fetched
+    pub fn fetch_seq_circular(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+        let invalid = || FastaError::InvalidCoordinates {
+            seqname: seqname.to_string(),
+            start,
+            end,
+        };
+
+        if start < 0 || start >= length || end < 0 {
+            return Err(invalid());
+        }
+
+        // Position on the sequence where the wrapped part ends
+        let wrapped_end = if start > end {
+            end
+        } else if end > length {
+            end - length
+        } else {
+            return self.fetch_seq(seqname, start, end);
+        };
+        if wrapped_end > start {
+            // The region would cover part of the sequence twice
+            return Err(invalid());
+        }
+
+        let mut sequence = self.fetch_seq(seqname, start, length)?;
+        if wrapped_end > 0 {
+            sequence.push_str(&self.fetch_seq(seqname, 0, wrapped_end)?);
+        }
+        Ok(sequence)
+    }
+
+    /// Load every sequence in the index, in index (reference) order
+    ///
+    /// This holds the entire FASTA in memory. For large references, open the
+    /// index with [`FastaIndex::new_filtered`] to load only the sequences
+    /// needed, or stream them with [`write_filtered`](Self::write_filtered).
+    ///
+    /// # Returns
+    ///
+    /// `(name, sequence)` pairs in the order sequences appear in the index
+    pub fn load_all_ordered(&self) -> FastaResult<Vec<(String, String)>> {
+        self._index
+            .sequence_names()
+            .into_iter()
+            .map(|name| {
+                let sequence = self.fetch_seq_all(&name)?;
+                Ok((name, sequence))
+            })
+            .collect()
+    }
+
+    /// Fetch every sequence whose name matches a glob pattern
+    ///
+    /// The pattern may use `*` (any run of characters) and `?` (any single
+    /// character). Sequences are matched in index order and fetched lazily as
+    /// the iterator advances.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Glob pattern matched against whole sequence names
+    ///
+    /// # Returns
+    ///
+    /// An iterator of `(name, sequence)` pairs, one per matching sequence
+    pub fn fetch_glob<'a>(
+        &'a self,
+        pattern: &'a str,
+    ) -> impl Iterator<Item = (String, FastaResult<String>)> + 'a {
+        self._index
+            .sequence_names()
+            .into_iter()
+            .filter(move |name| glob_match(pattern.as_bytes(), name.as_bytes()))
+            .map(move |name| {
+                let sequence = self.fetch_seq_all(&name);
+                (name, sequence)
+            })
+    }
+
+    /// Fetch quality scores for the specified region (FASTQ only)
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The quality string or an error if the quality cannot be fetched
+    pub fn fetch_qual(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        if let Some(timeout) = self.timeout {
+            let seqname = seqname.to_string();
+            return self.fetch_with_timeout(timeout, move |reader| {
+                reader.fetch_qual(&seqname, start, end)
+            });
+        }
+        if self._index.selected.is_some() && !self._index.has_sequence(seqname) {
+            return Err(FastaError::SequenceNotFound(seqname.to_string()));
+        }
+
+        let c_seqname =
+            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+        self.fetch_c_qual(&c_seqname, start, end)
+    }
+
+    /// Fetch quality scores with the C copy of the name already built
+    fn fetch_c_qual(&self, c_seqname: &CStr, start: i64, end: i64) -> FastaResult<String> {
+        let mut len: i64 = 0;
+        let qual_ptr = unsafe {
+            faidx_reader_fetch_qual(self.reader, c_seqname.as_ptr(), start, end - 1, &mut len)
+        };
+
+        if qual_ptr.is_null() {
+            return Err(FastaError::QualityNotAvailable);
+        }
+
+        let c_str = unsafe { CStr::from_ptr(qual_ptr) };
+        let result = c_str.to_string_lossy().to_string();
+
+        unsafe {
+            libc::free(qual_ptr as *mut c_void);
+        }
+
+        Ok(result)
+    }
+
+    /// Fetch the sequence of a region together with its qualities
+    ///
+    /// For FASTQ indexes the quality string is returned alongside the
+    /// sequence and is guaranteed to have the same length: a malformed record
+    /// whose lengths differ fails with [`FastaError::LengthMismatch`]. For
+    /// FASTA indexes the quality is `None`. The sequence name is converted for
+    /// the C reader only once for both fetches.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence and optional quality string, or an error if either cannot be fetched
+    pub fn fetch_record(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<(String, Option<String>)> {
+        if self.timeout.is_some() {
+            let sequence = self.fetch_seq(seqname, start, end)?;
+            if !self._index.is_fastq() {
+                return Ok((sequence, None));
+            }
+            let quality = self.fetch_qual(seqname, start, end)?;
+            check_quality_length(&sequence, &quality)?;
+            return Ok((sequence, Some(quality)));
+        }
+
+        let c_seqname =
+            CString::new(seqname).map_err(|_| FastaError::SequenceNotFound(seqname.to_string()))?;
+        let mut buf = Vec::new();
+        self.fetch_c_seq_into(seqname, &c_seqname, start, end, &mut buf)?;
+        let sequence = String::from_utf8(buf)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+
+        if !self._index.is_fastq() {
+            return Ok((sequence, None));
+        }
+        let quality = self.fetch_c_qual(&c_seqname, start, end)?;
+        check_quality_length(&sequence, &quality)?;
+        Ok((sequence, Some(quality)))
+    }
+
+    /// Fetch a region as a named record
+    ///
+    /// The record is named after the region in samtools style, with 1-based
+    /// inclusive coordinates (e.g. `chr1:101-200` for `start = 100`,
+    /// `end = 200`), and carries qualities for FASTQ indexes as with
+    /// [`fetch_record`](Self::fetch_record).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The record, or an error if the sequence or qualities cannot be fetched
+    pub fn fetch_record_named(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<FastaRecord> {
+        let (seq, qual) = self.fetch_record(seqname, start, end)?;
+        let name = Region {
+            name: seqname.to_string(),
+            start: Some(start + 1),
+            end: Some(end),
+        }
+        .to_string();
+        Ok(FastaRecord { name, seq, qual })
+    }
+
+    /// Fetch Phred+33 quality scores as numeric values (FASTQ only)
+    ///
+    /// Equivalent to [`fetch_qual_scores_as`](Self::fetch_qual_scores_as)
+    /// with [`QualityEncoding::Phred33`].
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// One Phred score per base or an error if the quality cannot be fetched
+    pub fn fetch_qual_scores(&self, seqname: &str, start: i64, end: i64) -> FastaResult<Vec<u8>> {
+        self.fetch_qual_scores_as(seqname, start, end, QualityEncoding::Phred33)
+    }
+
+    /// Fetch quality scores as numeric Phred values in the given encoding (FASTQ only)
+    ///
+    /// Characters below the encoding offset, or encoding a score above 93,
+    /// indicate corrupt or misencoded data and are rejected with
+    /// [`FastaError::InvalidQuality`].
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `encoding` - Encoding of the quality string (`Phred33` or `Phred64`)
+    ///
+    /// # Returns
+    ///
+    /// One Phred score per base or an error if the quality cannot be fetched
+    /// or decoded
+    pub fn fetch_qual_scores_as(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        encoding: QualityEncoding,
+    ) -> FastaResult<Vec<u8>> {
+        let offset = match encoding {
+            QualityEncoding::Phred33 => 33,
+            QualityEncoding::Phred64 => 64,
+            QualityEncoding::Ambiguous => {
+                return Err(FastaError::InvalidParameter(
+                    "quality encoding must be Phred33 or Phred64".to_string(),
+                ))
+            }
+        };
+        let quals = self.fetch_qual(seqname, start, end)?;
+        phred_scores(quals.as_bytes(), offset, start)
+    }
+
+    /// Compute the mean Phred+33 quality of a region (FASTQ only)
+    ///
+    /// This is the arithmetic mean of the Phred scores, as used for read
+    /// filtering, not the mean error probability.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The mean score (0.0 for an empty region), or
+    /// [`FastaError::QualityNotAvailable`] for FASTA indexes
+    pub fn mean_qual(&self, seqname: &str, start: i64, end: i64) -> FastaResult<f64> {
+        let scores = self.fetch_qual_scores(seqname, start, end)?;
+        Ok(mean_score(&scores))
+    }
+
+    /// Fetch a FASTQ region as it reads on the reverse strand (FASTQ only)
+    ///
+    /// The sequence is reverse-complemented and the quality string is
+    /// reversed (but not complemented), so each quality still pairs with its base.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The reverse-complemented sequence and reversed quality string, an
+    /// error if either cannot be fetched, or [`FastaError::LengthMismatch`]
+    /// if their lengths differ
+    pub fn fetch_seq_qual_revcomp(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<(String, String)> {
+        let seq = self.fetch_seq(seqname, start, end)?;
+        let qual = self.fetch_qual(seqname, start, end)?;
+        check_quality_length(&seq, &qual)?;
+
+        Ok((reverse_complement(&seq), qual.chars().rev().collect()))
+    }
+
+    /// Detect the quality encoding of a FASTQ file (FASTQ only)
+    ///
+    /// Quality strings from the first few records are sampled and the
+    /// encoding is inferred from the observed ASCII range: characters below
+    /// `;` (59) only occur with Phred+33, while a range starting at `@` (64)
+    /// and extending past `J` (74) indicates Phred+64.
+    ///
+    /// # Returns
+    ///
+    /// The detected encoding, [`QualityEncoding::Ambiguous`] if the sample
+    /// cannot distinguish the two, or an error if the quality cannot be fetched
+    pub fn detect_quality_encoding(&self) -> FastaResult<QualityEncoding> {
+        const SAMPLE_RECORDS: usize = 16;
+
+        let mut observed = Vec::new();
+        for name in self._index.sequence_names().iter().take(SAMPLE_RECORDS) {
+            let length = self
+                ._index
+                .sequence_length(name)
+                .ok_or_else(|| FastaError::SequenceNotFound(name.clone()))?;
+            if length > 0 {
+                observed.extend(self.fetch_qual(name, 0, length)?.into_bytes());
+            }
+        }
+
+        Ok(infer_quality_encoding(&observed))
+    }
+
+    /// Parse a region string (e.g., "chr1:1000-2000") and fetch the sequence
+    ///
+    /// Coordinates are 1-based and inclusive, as in samtools, so "chr1:1-10"
+    /// returns the first 10 bases of chr1 (unlike the 0-based, half-open
+    /// coordinates taken by [`fetch_seq`](Self::fetch_seq)). Either bound may
+    /// be omitted: "chr1:1000" and "chr1:1000-" extend to the end of the
+    /// sequence and "chr1:-2000" starts at the beginning. Sequence names may
+    /// contain colons (e.g. "HLA:A*01:01"): the region is split at the last
+    /// colon, and only if the whole string is not itself a sequence name.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - Region string in format "seqname:start-end"
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the region cannot be parsed or fetched
+    pub fn fetch_region(&self, region: &str) -> FastaResult<String> {
+        let region = self._index.parse_region(region)?;
+        if region.start.is_none() && region.end.is_none() {
+            return self.fetch_seq_all(&region.name);
+        }
+
+        let length = self
+            ._index
+            .sequence_length(&region.name)
+            .ok_or_else(|| FastaError::SequenceNotFound(region.name.clone()))?;
+        let (start, end) = region.bounds(length);
+        self.fetch_seq(&region.name, start, end)
+    }
+
+    /// Fetch a region parsed with htslib's region-string rules
+    ///
+    /// The string is parsed by the C layer following htslib's
+    /// `fai_parse_region`, for callers that need exact samtools behaviour
+    /// rather than [`fetch_region`](Self::fetch_region)'s parser. Beyond the
+    /// forms both accept, this supports `{name}` quoting for names containing
+    /// colons, k/M/G suffixes (`chr1:1.5k-2k`), and rejects a region whose
+    /// full string and pre-colon part both name sequences as ambiguous.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - Region string, 1-based and inclusive as in samtools
+    ///
+    /// # Returns
+    ///
+    /// The sequence string or an error if the region cannot be parsed or fetched
+    pub fn fetch_htslib_region(&self, region: &str) -> FastaResult<String> {
+        let invalid = || FastaError::InvalidRegion(region.to_string());
+        let c_region = CString::new(region).map_err(|_| invalid())?;
+
+        let (mut tid, mut beg, mut end): (c_int, i64, i64) = (0, 0, 0);
+        let parsed = unsafe {
+            faidx_meta_parse_region(
+                self._index.meta,
+                c_region.as_ptr(),
+                &mut tid,
+                &mut beg,
+                &mut end,
+            )
+        };
+        if parsed.is_null() {
+            return Err(invalid());
+        }
+
+        let name_ptr = unsafe { faidx_meta_iseq(self._index.meta, tid) };
+        if name_ptr.is_null() {
+            return Err(invalid());
+        }
+        let seqname = unsafe { CStr::from_ptr(name_ptr) }
+            .to_string_lossy()
+            .into_owned();
+        let length = self
+            ._index
+            .sequence_length(&seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.clone()))?;
+        self.fetch_seq(&seqname, beg, end.min(length))
+    }
+
+    /// Fetch a region split into lines of at most `width` characters
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `width` - Maximum number of characters per line
+    ///
+    /// # Returns
+    ///
+    /// The sequence as `width`-character lines (the last line may be shorter)
+    pub fn fetch_seq_lines(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        width: usize,
+    ) -> FastaResult<Vec<String>> {
+        if width == 0 {
+            return Err(FastaError::InvalidParameter(
+                "line width must be at least 1".to_string(),
+            ));
+        }
+
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        Ok(sequence
+            .as_bytes()
+            .chunks(width)
+            .map(|line| String::from_utf8_lossy(line).into_owned())
+            .collect())
+    }
+
+    /// Fetch a region along with its soft-masking state
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The sequence string and a parallel vector that is `true` for each
+    /// soft-masked (lowercase) base
+    pub fn fetch_seq_with_mask_bits(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<(String, Vec<bool>)> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let mask = sequence.bytes().map(|b| b.is_ascii_lowercase()).collect();
+        Ok((sequence, mask))
+    }
+
+    /// Fetch a region, soft-masking the bases inside the given intervals
+    ///
+    /// Bases overlapping any mask interval are lowercased, like
+    /// `bedtools maskfasta -soft`. Mask intervals are 0-based, half-open
+    /// positions on the same sequence; they may be unsorted, overlap each
+    /// other or extend past the region. Empty intervals mask nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `mask` - `(start, end)` intervals to soft-mask
+    ///
+    /// # Returns
+    ///
+    /// The masked sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_seq_masked(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        mask: &[(i64, i64)],
+    ) -> FastaResult<String> {
+        self.fetch_with_mask(seqname, start, end, mask, |bases| {
+            bases.make_ascii_lowercase()
+        })
+    }
+
+    /// Fetch a region, hard-masking the bases inside the given intervals
+    ///
+    /// Bases overlapping any mask interval are replaced by `mask_char`, like
+    /// `bedtools maskfasta` (which uses `b'N'`). Mask intervals are handled
+    /// as in [`fetch_seq_masked`](Self::fetch_seq_masked).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `mask` - `(start, end)` intervals to hard-mask
+    /// * `mask_char` - Printable ASCII character replacing masked bases
+    ///
+    /// # Returns
+    ///
+    /// The masked sequence string, or an error if `mask_char` is not printable
+    /// ASCII or the sequence cannot be fetched
+    pub fn fetch_seq_hardmasked(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        mask: &[(i64, i64)],
+        mask_char: u8,
+    ) -> FastaResult<String> {
+        if !mask_char.is_ascii_graphic() {
+            return Err(FastaError::InvalidParameter(format!(
+                "mask character must be printable ASCII, got byte {:#04x}",
+                mask_char
+            )));
+        }
+        self.fetch_with_mask(seqname, start, end, mask, |bases| bases.fill(mask_char))
+    }
+
+    /// Fetch a region and apply `mask_bases` to each part overlapping a mask interval
+    fn fetch_with_mask(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        mask: &[(i64, i64)],
+        mask_bases: impl Fn(&mut [u8]),
+    ) -> FastaResult<String> {
+        let mut bytes = self.fetch_seq_bytes(seqname, start, end)?;
+        let fetched_end = start + bytes.len() as i64;
+        for &(mask_start, mask_end) in mask {
+            let from = mask_start.max(start);
+            let to = mask_end.min(fetched_end);
+            if from < to {
+                mask_bases(&mut bytes[(from - start) as usize..(to - start) as usize]);
+            }
+        }
+        Ok(String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+    }
+
+    /// Find the soft-masked (lowercase) intervals of a sequence
+    ///
+    /// The whole sequence is scanned in blocks, so memory use is bounded by
+    /// the number of intervals rather than the sequence length. This is the
+    /// inverse of [`fetch_seq_masked`](Self::fetch_seq_masked).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    ///
+    /// # Returns
+    ///
+    /// Half-open `(start, end)` intervals of contiguous lowercase bases, in order
+    pub fn masked_intervals(&self, seqname: &str) -> FastaResult<Vec<(i64, i64)>> {
+        self.base_runs(seqname, |b| b.is_ascii_lowercase())
+    }
+
+    /// Find the assembly gaps (runs of N) of a sequence
+    ///
+    /// N and n are both gap bases. Like
+    /// [`masked_intervals`](Self::masked_intervals), the sequence is scanned
+    /// in blocks; the result is suitable for writing out as a gaps BED file.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `min_len` - Minimum gap length to report
+    ///
+    /// # Returns
+    ///
+    /// Half-open `(start, end)` intervals of at least `min_len` N bases, in order
+    pub fn n_gaps(&self, seqname: &str, min_len: i64) -> FastaResult<Vec<(i64, i64)>> {
+        let mut gaps = self.base_runs(seqname, |b| b.eq_ignore_ascii_case(&b'N'))?;
+        gaps.retain(|&(start, end)| end - start >= min_len);
+        Ok(gaps)
+    }
+
+    /// Collect the maximal runs of bases accepted by `in_run` over a whole sequence
+    fn base_runs(
+        &self,
+        seqname: &str,
+        in_run: impl Fn(u8) -> bool,
+    ) -> FastaResult<Vec<(i64, i64)>> {
+        let mut runs = Vec::new();
+        let mut pos = 0i64;
+        let mut run_start: Option<i64> = None;
+
+        self.for_each_block(seqname, |block| {
+            for &b in block {
+                match (in_run(b), run_start) {
+                    (true, None) => run_start = Some(pos),
+                    (false, Some(start)) => {
+                        runs.push((start, pos));
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+            Ok(())
+        })?;
+        if let Some(start) = run_start {
+            runs.push((start, pos));
+        }
+
+        Ok(runs)
+    }
+
+    /// Fetch the reverse complement of a region
+    ///
+    /// The coordinates select the forward-strand region as in
+    /// [`fetch_seq`](Self::fetch_seq). Bases are complemented using the full
+    /// IUPAC alphabet (R/Y, K/M, B/V and D/H swap; S, W and N are their own
+    /// complements), and case is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The reverse-complemented sequence or an error if the sequence cannot be fetched
+    pub fn fetch_seq_revcomp(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        Ok(reverse_complement(&sequence))
+    }
+
+    /// Fetch the complement of a region without reversing it
+    ///
+    /// Each base is complemented as in [`fetch_seq_revcomp`](Self::fetch_seq_revcomp),
+    /// using the full IUPAC alphabet and preserving case, but the bases keep
+    /// their forward-strand order.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The complemented sequence or an error if the sequence cannot be fetched
+    pub fn fetch_seq_complement(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        Ok(sequence.bytes().map(|b| complement(b) as char).collect())
+    }
+
+    /// Extract the sequence of every interval in BED input
+    ///
+    /// This is `bedtools getfasta -s` as a library call: intervals on the `-`
+    /// strand are reverse-complemented, and blank, `#` comment, `track` and
+    /// `browser` lines are skipped. Lines are read and fetched lazily.
+    ///
+    /// # Arguments
+    ///
+    /// * `bed` - BED data (chrom, start, end and optional name, score and strand columns)
+    ///
+    /// # Returns
+    ///
+    /// An iterator of `(label, sequence)` pairs in input order, where the
+    /// label is the name column if present and `chrom:start-end` otherwise.
+    /// Malformed lines and failed fetches yield an error without ending the
+    /// iteration.
+    pub fn fetch_bed<'a, R: std::io::Read + 'a>(
+        &'a self,
+        bed: R,
+    ) -> impl Iterator<Item = FastaResult<(String, String)>> + 'a {
+        use std::io::BufRead;
+
+        std::io::BufReader::new(bed)
+            .lines()
+            .filter_map(move |line| match line {
+                Ok(line) => BedRecord::parse_line(&line),
+                Err(source) => Some(Err(FastaError::Io {
+                    context: "reading BED input".to_string(),
+                    source,
+                })),
+            })
+            .map(move |record| {
+                let record = record?;
+                let sequence = self.fetch_stranded(
+                    &record.chrom,
+                    record.start,
+                    record.end,
+                    record.read_strand(),
+                )?;
+                Ok((record.label(), sequence))
+            })
+    }
+
+    /// Write the sequence of every interval in BED input to `out` as FASTA
+    ///
+    /// Headers use the BED name column when present and `chrom:start-end`
+    /// otherwise, like `bedtools getfasta -name`. Intervals are fetched as in
+    /// [`fetch_bed`](Self::fetch_bed).
+    ///
+    /// # Arguments
+    ///
+    /// * `bed` - BED data (chrom, start, end and optional name, score and strand columns)
+    /// * `out` - Destination for the FASTA records
+    /// * `line_width` - Number of bases per output line (0 for no wrapping)
+    ///
+    /// # Returns
+    ///
+    /// The number of records written, or the first parse, fetch or write error
+    pub fn write_fasta_from_bed<R: std::io::Read, W: std::io::Write>(
+        &self,
+        bed: R,
+        out: &mut W,
+        line_width: usize,
+    ) -> FastaResult<usize> {
+        let mut written = 0;
+        for result in self.fetch_bed(bed) {
+            let (label, sequence) = result?;
+            write_fasta_record(out, &label, sequence.as_bytes(), line_width).map_err(|source| {
+                FastaError::Io {
+                    context: "writing FASTA output".to_string(),
+                    source,
+                }
+            })?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Fetch a region as it reads on the given strand
+    ///
+    /// Returns the forward sequence for [`Strand::Forward`] and the reverse
+    /// complement for [`Strand::Reverse`], like `bedtools getfasta -s`. The
+    /// coordinates are always 0-based, half-open positions on the forward
+    /// strand.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `strand` - Strand to read the region from
+    ///
+    /// # Returns
+    ///
+    /// The stranded sequence or an error if the sequence cannot be fetched
+    pub fn fetch_stranded(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        strand: Strand,
+    ) -> FastaResult<String> {
+        match strand {
+            Strand::Forward => self.fetch_seq(seqname, start, end),
+            Strand::Reverse => self.fetch_seq_revcomp(seqname, start, end),
+        }
+    }
+
+    /// Fetch a spliced sequence from exon intervals of one sequence
+    ///
+    /// The exons are joined in forward-strand coordinate order, whatever order
+    /// they are given in. For [`Strand::Reverse`] the joined sequence is
+    /// reverse-complemented, so the result reads 5' to 3' on the transcript's
+    /// strand, as when extracting transcripts from a GFF.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `exons` - `(start, end)` intervals (0-based, half-open)
+    /// * `strand` - Strand of the transcript
+    ///
+    /// # Returns
+    ///
+    /// The spliced sequence or an error if any exon cannot be fetched
+    pub fn fetch_spliced(
+        &self,
+        seqname: &str,
+        exons: &[(i64, i64)],
+        strand: Strand,
+    ) -> FastaResult<String> {
+        let mut ordered = exons.to_vec();
+        ordered.sort_unstable();
+
+        let mut spliced = String::new();
+        for (start, end) in ordered {
+            spliced.push_str(&self.fetch_seq(seqname, start, end)?);
+        }
+        Ok(match strand {
+            Strand::Forward => spliced,
+            Strand::Reverse => reverse_complement(&spliced),
+        })
+    }
+
+    /// Fetch a region normalized to the given nucleic acid alphabet
+    ///
+    /// Fetching as [`NucleicAcid::Rna`] transcribes T to U, and fetching as
+    /// [`NucleicAcid::Dna`] reverse-transcribes U to T. Case is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `alphabet` - Target alphabet
+    ///
+    /// # Returns
+    ///
+    /// The normalized sequence string or an error if the sequence cannot be fetched
+    pub fn fetch_seq_as(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        alphabet: NucleicAcid,
+    ) -> FastaResult<String> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let (from, to) = match alphabet {
+            NucleicAcid::Dna => (b'U', b'T'),
+            NucleicAcid::Rna => (b'T', b'U'),
+        };
+
+        let normalized = sequence
+            .bytes()
+            .map(|b| {
+                if b == from {
+                    to
+                } else if b == from.to_ascii_lowercase() {
+                    to.to_ascii_lowercase()
+                } else {
+                    b
+                }
+            })
+            .collect();
+        Ok(String::from_utf8(normalized).expect("substitution preserves UTF-8"))
+    }
+
+    /// Fetch a region as a 2-bit packed sequence
+    ///
+    /// Bases are encoded as A=0, C=1, G=2, T=3 (case-insensitive) and packed
+    /// four per byte, with the first base in the most significant bits. The
+    /// final byte is zero-padded when the base count is not a multiple of four.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The packed bytes and the number of bases, or an error if the region
+    /// contains a base other than A/C/G/T
+    pub fn fetch_seq_2bit(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+    ) -> FastaResult<(Vec<u8>, usize)> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let offset = start.max(0);
+
+        let mut packed = vec![0u8; (sequence.len() + 3) / 4];
+        for (i, base) in sequence.bytes().enumerate() {
+            let code =
+                nt_to_2bit(base).ok_or(FastaError::NonAcgtBase(base as char, offset + i as i64))?;
+            packed[i / 4] |= (code as u8) << (6 - 2 * (i % 4));
+        }
+
+        Ok((packed, sequence.len()))
+    }
+
+    /// Count mismatches between the reference and a query sequence
+    ///
+    /// The reference window `[start, start + query.len())` is compared to
+    /// `query` case-insensitively. The window is fetched in chunks, and
+    /// counting stops once more than `max_mismatches` mismatches are found,
+    /// so rejecting a poor match does not read the rest of the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position of the window (0-based)
+    /// * `query` - Bases to compare against the reference
+    /// * `n_is_wildcard` - Whether an `N` in either sequence matches any base;
+    ///   when `false`, `N` only matches `N`
+    /// * `max_mismatches` - Stop counting after this many mismatches, or
+    ///   `None` to count them all
+    ///
+    /// # Returns
+    ///
+    /// The number of mismatching positions (`max_mismatches + 1` if counting
+    /// stopped early), or an error if the window does not lie within the sequence
+    pub fn count_mismatches(
+        &self,
+        seqname: &str,
+        start: i64,
+        query: &[u8],
+        n_is_wildcard: bool,
+        max_mismatches: Option<usize>,
+    ) -> FastaResult<usize> {
+        const CHUNK: usize = 4096;
+
+        if query.is_empty() {
+            return Ok(0);
+        }
+
+        let end = start + query.len() as i64;
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+        if start < 0 || end > length {
+            return Err(FastaError::InvalidCoordinates {
+                seqname: seqname.to_string(),
+                start,
+                end,
+            });
+        }
+
+        let limit = max_mismatches.unwrap_or(usize::MAX);
+        let mut mismatches = 0;
+        for (i, chunk) in query.chunks(CHUNK).enumerate() {
+            let chunk_start = start + (i * CHUNK) as i64;
+            let reference =
+                self.fetch_seq_bytes(seqname, chunk_start, chunk_start + chunk.len() as i64)?;
+            mismatches += base_mismatches(&reference, chunk, n_is_wildcard, limit - mismatches);
+            if mismatches > limit {
+                break;
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Write every sequence accepted by `keep` to `out` as FASTA
+    ///
+    /// Sequences are streamed in index order, a block of lines at a time, so
+    /// memory use is bounded regardless of sequence length.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Destination for the FASTA records
+    /// * `keep` - Predicate on `(name, length)` selecting the sequences to write
+    /// * `line_width` - Number of bases per output line
+    ///
+    /// # Returns
+    ///
+    /// The total number of bytes written
+    pub fn write_filtered<W: std::io::Write>(
+        &self,
+        out: &mut W,
+        keep: impl Fn(&str, i64) -> bool,
+        line_width: usize,
+    ) -> FastaResult<u64> {
+        if line_width == 0 {
+            return Err(FastaError::InvalidParameter(
+                "line width must be at least 1".to_string(),
+            ));
+        }
+
+        let io_err = |source: std::io::Error| FastaError::Io {
+            context: "writing FASTA output".to_string(),
+            source,
+        };
+        let mut written = 0u64;
+
+        for name in self._index.sequence_names() {
+            let length = self
+                ._index
+                .sequence_length(&name)
+                .ok_or_else(|| FastaError::SequenceNotFound(name.clone()))?;
+            if !keep(&name, length) {
+                continue;
+            }
+
+            writeln!(out, ">{}", name).map_err(io_err)?;
+            written += name.len() as u64 + 2;
+
+            let mut column = 0;
+            self.for_each_block(&name, |mut rest| {
+                while !rest.is_empty() {
+                    let take = (line_width - column).min(rest.len());
+                    out.write_all(&rest[..take]).map_err(io_err)?;
+                    written += take as u64;
+                    rest = &rest[take..];
+                    column += take;
+                    if column == line_width {
+                        out.write_all(b"\n").map_err(io_err)?;
+                        written += 1;
+                        column = 0;
+                    }
+                }
+                Ok(())
+            })?;
+            if column > 0 {
+                out.write_all(b"\n").map_err(io_err)?;
+                written += 1;
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Count the bases in a region
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `split_masked` - Count soft-masked (lowercase) bases separately;
+    ///   when `false`, every base is counted as unmasked
+    ///
+    /// # Returns
+    ///
+    /// The base composition or an error if the sequence cannot be fetched
+    pub fn count_bases(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        split_masked: bool,
+    ) -> FastaResult<BaseCounts> {
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let mut counts = BaseCounts::default();
+        counts.tally(sequence.as_bytes(), split_masked);
+        Ok(counts)
+    }
+
+    /// Count the bases of every sequence in the file
+    ///
+    /// Sequences are streamed one block at a time in a single pass, so memory
+    /// use stays bounded however large the genome is. Lowercase bases are
+    /// counted as masked.
+    ///
+    /// # Returns
+    ///
+    /// The genome-wide base composition or an error if a sequence cannot be fetched
+    pub fn genome_composition(&self) -> FastaResult<Composition> {
+        let mut counts = BaseCounts::default();
+        for name in self._index.sequence_names() {
+            self.for_each_block(&name, |block| {
+                counts.tally(block, true);
+                Ok(())
+            })?;
+        }
+        Ok(counts)
+    }
+
+    /// Count the N bases (either case) in a region
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The number of N bases or an error if the sequence cannot be fetched
+    pub fn count_ns(&self, seqname: &str, start: i64, end: i64) -> FastaResult<u64> {
+        let counts = self.count_bases(seqname, start, end, false)?;
+        Ok(counts.n.total() as u64)
+    }
+
+    /// Count the A, C, G, T and other bases in a region
+    ///
+    /// Counting is case-insensitive and U is counted as T. The last entry
+    /// covers everything else, including N and other ambiguity codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The counts as `[A, C, G, T, other]` or an error if the sequence cannot be fetched
+    pub fn base_composition(&self, seqname: &str, start: i64, end: i64) -> FastaResult<[u64; 5]> {
+        let counts = self.count_bases(seqname, start, end, false)?;
+        Ok([
+            counts.a.total() as u64,
+            counts.c.total() as u64,
+            counts.g.total() as u64,
+            counts.t.total() as u64,
+            (counts.n.total() + counts.s.total() + counts.other.total()) as u64,
+        ])
+    }
+
+    /// Compute the GC content of a region
+    ///
+    /// The numerator counts G, C and S (strong, G or C) bases; the
+    /// denominator counts every base except N, so unknown bases do not
+    /// dilute the result. Other ambiguity codes count towards the
+    /// denominator only. Matching is case-insensitive. This is
+    /// [`BaseCounts::gc_fraction`] of the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    ///
+    /// # Returns
+    ///
+    /// The GC fraction in `[0, 1]` (0.0 for a region that is empty or all N),
+    /// or an error if the sequence cannot be fetched
+    pub fn gc_content(&self, seqname: &str, start: i64, end: i64) -> FastaResult<f64> {
+        let sequence = self.fetch_seq_bytes(seqname, start, end)?;
+        let mut counts = BaseCounts::default();
+        counts.tally(&sequence, false);
+        Ok(counts.gc_fraction())
+    }
+
+    /// Find all occurrences of a motif within a region
+    ///
+    /// Matching is case-insensitive and overlapping matches are all reported.
+    /// The motif may contain IUPAC ambiguity codes (e.g. `NGG` for a Cas9 PAM),
+    /// each matching any of the bases it represents. With `both_strands`, the
+    /// motif's reverse complement is matched too; every position is the
+    /// forward-strand start of the matched bases, so a palindromic site such
+    /// as `GAATTC` is reported once.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `motif` - Motif to search for, read 5' to 3'
+    /// * `both_strands` - Whether to also report reverse-strand occurrences
+    ///
+    /// # Returns
+    ///
+    /// The sorted 0-based positions on the sequence where a match starts
+    pub fn find_motif(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        motif: &[u8],
+        both_strands: bool,
+    ) -> FastaResult<Vec<i64>> {
+        if motif.is_empty() {
+            return Err(FastaError::InvalidParameter(
+                "motif must not be empty".to_string(),
+            ));
+        }
+
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let mut positions: Vec<i64> = motif_matches(sequence.as_bytes(), motif)
+            .map(|i| start + i as i64)
+            .collect();
+        if both_strands {
+            let revcomp: Vec<u8> = motif.iter().rev().map(|&b| complement(b)).collect();
+            positions
+                .extend(motif_matches(sequence.as_bytes(), &revcomp).map(|i| start + i as i64));
+            positions.sort_unstable();
+            positions.dedup();
+        }
+        Ok(positions)
+    }
+
+    /// Visit a whole sequence in blocks of whole source lines, bounding memory use
+    fn for_each_block(
+        &self,
+        seqname: &str,
+        mut visit: impl FnMut(&[u8]) -> FastaResult<()>,
+    ) -> FastaResult<()> {
+        for (start, end) in self.block_ranges(seqname)? {
+            visit(self.fetch_seq(seqname, start, end)?.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Split a whole sequence into `(start, end)` blocks of whole source lines
+    fn block_ranges(&self, seqname: &str) -> FastaResult<Vec<(i64, i64)>> {
+        const BLOCK_LINES: i64 = 1024;
+
+        let length = self
+            ._index
+            .sequence_length(seqname)
+            .ok_or_else(|| FastaError::SequenceNotFound(seqname.to_string()))?;
+        let source_width = self
+            ._index
+            .entry(seqname)
+            .map_or(0, |entry| entry.line_blen as i64)
+            .max(1);
+        let block = source_width * BLOCK_LINES;
+
+        Ok((0..length)
+            .step_by(block as usize)
+            .map(|start| (start, (start + block).min(length)))
+            .collect())
+    }
+
+    /// Translate a whole sequence in all six reading frames
+    ///
+    /// Frames are returned in the order +1, +2, +3, -1, -2, -3: the forward
+    /// frames start at offsets 0, 1 and 2 of the sequence, and the reverse
+    /// frames at offsets 0, 1 and 2 of its reverse complement. Translation
+    /// uses the standard genetic code, with `*` for stop codons and `X` for
+    /// codons containing ambiguous bases; trailing partial codons are dropped.
+    /// The sequence is streamed in blocks, so only the proteins are held in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    ///
+    /// # Returns
+    ///
+    /// The six protein translations or an error if the sequence cannot be fetched
+    pub fn six_frame_translate(&self, seqname: &str) -> FastaResult<[String; 6]> {
+        let blocks = self.block_ranges(seqname)?;
+        let mut forward = FrameTranslator::default();
+        let mut reverse = FrameTranslator::default();
+
+        for &(start, end) in &blocks {
+            forward.extend(self.fetch_seq(seqname, start, end)?.bytes());
+        }
+        for &(start, end) in blocks.iter().rev() {
+            let seq = self.fetch_seq(seqname, start, end)?;
+            reverse.extend(seq.bytes().rev().map(complement));
+        }
+
+        let [f1, f2, f3] = forward.proteins;
+        let [r1, r2, r3] = reverse.proteins;
+        Ok([f1, f2, f3, r1, r2, r3])
+    }
+
+    /// Translate a region to protein with the standard genetic code
+    ///
+    /// Codons are read from `start + frame` on the forward strand using NCBI
+    /// table 1, with `*` for stop codons and `X` for codons containing
+    /// ambiguous bases; a trailing partial codon is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `frame` - Reading frame offset (0, 1 or 2)
+    ///
+    /// # Returns
+    ///
+    /// The amino acid string or an error if the frame is invalid or the
+    /// sequence cannot be fetched
+    pub fn fetch_protein(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        frame: u8,
+    ) -> FastaResult<String> {
+        self.fetch_protein_with_table(seqname, start, end, frame, 1)
+    }
+
+    /// Translate a region to protein with an alternative genetic code
+    ///
+    /// Like [`fetch_protein`](Self::fetch_protein), with the NCBI translation
+    /// table selected by number. Supported tables are 1 (standard), 2
+    /// (vertebrate mitochondrial), 3 (yeast mitochondrial), 4 (mold and
+    /// protozoan mitochondrial, Mycoplasma), 5 (invertebrate mitochondrial)
+    /// and 11 (bacterial and plastid).
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `frame` - Reading frame offset (0, 1 or 2)
+    /// * `table` - NCBI translation table number
+    ///
+    /// # Returns
+    ///
+    /// The amino acid string or an error if the frame or table is invalid or
+    /// the sequence cannot be fetched
+    pub fn fetch_protein_with_table(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        frame: u8,
+        table: u8,
+    ) -> FastaResult<String> {
+        if frame > 2 {
+            return Err(FastaError::InvalidParameter(format!(
+                "reading frame must be 0, 1 or 2, got {}",
+                frame
+            )));
+        }
+        let code = genetic_code(table).ok_or_else(|| {
+            FastaError::InvalidParameter(format!("unsupported genetic code table {}", table))
+        })?;
+
+        let bases = self.fetch_seq_bytes(seqname, start, end)?;
+        Ok(bases
+            .get(frame as usize..)
+            .unwrap_or_default()
+            .chunks_exact(3)
+            .map(|codon| translate_codon([codon[0], codon[1], codon[2]], code) as char)
+            .collect())
+    }
+
+    /// Compute the minimizer sketch of the specified region
+    ///
+    /// Canonical k-mers (the smaller of the forward and reverse-complement
+    /// encodings) are hashed with an invertible integer hash, and the minimum
+    /// hash in every window of `w` consecutive k-mers is selected. Ties within
+    /// a window resolve to the leftmost k-mer. K-mers containing bases other
+    /// than A/C/G/T are skipped and restart the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `k` - K-mer size (1..=32)
+    /// * `w` - Window size in k-mers (at least 1)
+    ///
+    /// # Returns
+    ///
+    /// `(minimizer_hash, position)` pairs in position order, where `position`
+    /// is the 0-based start of the k-mer on the sequence
+    pub fn minimizers(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        k: usize,
+        w: usize,
+    ) -> FastaResult<Vec<(u64, i64)>> {
+        if k == 0 || k > 32 {
+            return Err(FastaError::InvalidParameter(format!(
+                "k-mer size must be between 1 and 32, got {}",
+                k
+            )));
+        }
+        if w == 0 {
+            return Err(FastaError::InvalidParameter(
+                "window size must be at least 1".to_string(),
+            ));
+        }
+
+        let sequence = self.fetch_seq(seqname, start, end)?;
+        let offset = start.max(0);
+
+        Ok(minimizer_sketch(sequence.as_bytes(), k, w)
+            .into_iter()
+            .map(|(hash, pos)| (hash, offset + pos as i64))
+            .collect())
+    }
+
+    /// Iterate over the k-mers of a region
+    ///
+    /// The region is fetched once, and its overlapping length-`k` substrings
+    /// are yielded in position order, as fetched (case is preserved). A
+    /// region shorter than `k` has no k-mers. With `skip_ambiguous`, k-mers
+    /// containing any base other than A/C/G/T (such as `N`) are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `seqname` - Name of the sequence
+    /// * `start` - Start position (0-based, inclusive)
+    /// * `end` - End position (0-based, exclusive)
+    /// * `k` - K-mer size (at least 1)
+    /// * `skip_ambiguous` - Whether to skip k-mers with non-ACGT bases
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the k-mers, or an error if `k` is 0 or the region
+    /// cannot be fetched
+    pub fn kmers(
+        &self,
+        seqname: &str,
+        start: i64,
+        end: i64,
+        k: usize,
+        skip_ambiguous: bool,
+    ) -> FastaResult<impl Iterator<Item = Vec<u8>>> {
+        if k == 0 {
+            return Err(FastaError::InvalidParameter(
+                "k-mer size must be at least 1".to_string(),
+            ));
+        }
+
+        let bases = self.fetch_seq_bytes(seqname, start, end)?;
+        let count = (bases.len() + 1).saturating_sub(k);
+        Ok((0..count).filter_map(move |i| {
+            let kmer = &bases[i..i + k];
+            if skip_ambiguous && kmer.iter().any(|&b| nt_to_2bit(b).is_none()) {
+                None
+            } else {
+                Some(kmer.to_vec())
+            }
+        }))
+    }
+}
+
+impl Drop for FastaReader {
+    fn drop(&mut self) {
+        unsafe {
+            faidx_reader_destroy(self.reader);
+        }
+    }
+}
+
+unsafe impl Send for FastaReader {}
+
+/// A [`FastaReader`] behind a mutex, shareable across threads
+///
+/// Fetches from all threads are serialized on one file handle, so this
+/// trades throughput for the convenience of a single `Arc<SyncFastaReader>`.
+/// Use one `FastaReader` per thread where fetch throughput matters.
+pub struct SyncFastaReader {
+    reader: std::sync::Mutex<FastaReader>,
+}
+
+impl SyncFastaReader {
+    /// Create a new shareable reader from an index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Shared FASTA index
+    ///
+    /// # Returns
+    ///
+    /// A new `SyncFastaReader` instance or an error if the reader cannot be created
+    pub fn new(index: &FastaIndex) -> FastaResult<Self> {
+        Ok(FastaReader::new(index)?.into())
+    }
+
+    /// Run `f` with exclusive access to the underlying reader
+    ///
+    /// Gives access to the full [`FastaReader`] API; other threads block
+    /// until `f` returns.
+    pub fn with_reader<T>(&self, f: impl FnOnce(&FastaReader) -> T) -> T {
+        // A panic in another thread's closure leaves the reader usable, as
+        // every fetch seeks before reading
+        let reader = self
+            .reader
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&reader)
+    }
+
+    /// Fetch a sequence from the specified region
+    ///
+    /// See [`FastaReader::fetch_seq`].
+    pub fn fetch_seq(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        self.with_reader(|reader| reader.fetch_seq(seqname, start, end))
+    }
+
+    /// Fetch the entire sequence
+    ///
+    /// See [`FastaReader::fetch_seq_all`].
+    pub fn fetch_seq_all(&self, seqname: &str) -> FastaResult<String> {
+        self.with_reader(|reader| reader.fetch_seq_all(seqname))
+    }
+
+    /// Parse a region string (e.g., "chr1:1000-2000") and fetch the sequence
+    ///
+    /// See [`FastaReader::fetch_region`].
+    pub fn fetch_region(&self, region: &str) -> FastaResult<String> {
+        self.with_reader(|reader| reader.fetch_region(region))
+    }
+
+    /// Fetch quality scores for the specified region (FASTQ only)
+    ///
+    /// See [`FastaReader::fetch_qual`].
+    pub fn fetch_qual(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        self.with_reader(|reader| reader.fetch_qual(seqname, start, end))
+    }
+
+    /// Unwrap the underlying reader
+    pub fn into_inner(self) -> FastaReader {
+        self.reader
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl From<FastaReader> for SyncFastaReader {
+    fn from(reader: FastaReader) -> Self {
+        SyncFastaReader {
+            reader: std::sync::Mutex::new(reader),
+        }
+    }
+}
+
+/// A shareable pool of [`FastaReader`]s on one index
+///
+/// Readers are created on demand and returned to the pool when released, so
+/// each thread fetching concurrently gets its own file handle without
+/// managing reader lifetimes. The pool holds at most as many readers as the
+/// peak number of concurrent fetches.
+pub struct ReaderPool {
+    index: Arc<FastaIndex>,
+    idle: std::sync::Mutex<Vec<FastaReader>>,
+}
+
+impl ReaderPool {
+    /// Create an empty reader pool for an index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Shared FASTA index
+    pub fn new(index: &FastaIndex) -> Self {
+        ReaderPool {
+            index: Arc::new(index.clone()),
+            idle: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Check out a reader, creating one if none is idle
+    ///
+    /// The reader returns to the pool when the guard is dropped.
+    ///
+    /// # Returns
+    ///
+    /// A guard dereferencing to the reader, or an error if a new reader
+    /// cannot be created
+    pub fn get(&self) -> FastaResult<PooledReader<'_>> {
+        let idle = self
+            .idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop();
+        let reader = match idle {
+            Some(reader) => reader,
+            None => FastaReader::from_shared(Arc::clone(&self.index))?,
+        };
+        Ok(PooledReader {
+            pool: self,
+            reader: Some(reader),
+        })
+    }
+
+    /// Fetch a sequence from the specified region
+    ///
+    /// See [`FastaReader::fetch_seq`].
+    pub fn fetch_seq(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        self.get()?.fetch_seq(seqname, start, end)
+    }
+
+    /// Fetch the entire sequence
+    ///
+    /// See [`FastaReader::fetch_seq_all`].
+    pub fn fetch_seq_all(&self, seqname: &str) -> FastaResult<String> {
+        self.get()?.fetch_seq_all(seqname)
+    }
+
+    /// Parse a region string (e.g., "chr1:1000-2000") and fetch the sequence
+    ///
+    /// See [`FastaReader::fetch_region`].
+    pub fn fetch_region(&self, region: &str) -> FastaResult<String> {
+        self.get()?.fetch_region(region)
+    }
+
+    /// Fetch quality scores for the specified region (FASTQ only)
+    ///
+    /// See [`FastaReader::fetch_qual`].
+    pub fn fetch_qual(&self, seqname: &str, start: i64, end: i64) -> FastaResult<String> {
+        self.get()?.fetch_qual(seqname, start, end)
+    }
+
+    /// Number of readers currently waiting in the pool
+    pub fn idle_readers(&self) -> usize {
+        self.idle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+}
+
+/// A [`FastaReader`] checked out of a [`ReaderPool`]
+pub struct PooledReader<'a> {
+    pool: &'a ReaderPool,
+    // Always `Some` until dropped
+    reader: Option<FastaReader>,
+}
+
+impl std::ops::Deref for PooledReader<'_> {
+    type Target = FastaReader;
+
+    fn deref(&self) -> &FastaReader {
+        self.reader
+            .as_ref()
+            .expect("pooled reader already released")
+    }
+}
+
+impl Drop for PooledReader<'_> {
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            self.pool
+                .idle
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(reader);
+        }
+    }
+}
+
+/// Fetched sequence bytes in a C-allocated buffer
+///
+/// Returned by [`FastaReader::fetch_seq_buf`]. Dereferences to `[u8]` without
+/// copying, and frees the C buffer on drop.
+pub struct SeqBuf {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl SeqBuf {
+    fn empty() -> Self {
+        SeqBuf {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl std::ops::Deref for SeqBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        if self.ptr.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+        }
+    }
+}
+
+impl std::ops::DerefMut for SeqBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        if self.ptr.is_null() {
+            &mut []
+        } else {
+            unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+}
+
+impl std::fmt::Debug for SeqBuf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeqBuf").field("len", &self.len).finish()
+    }
+}
+
+impl Drop for SeqBuf {
+    fn drop(&mut self) {
+        unsafe {
+            libc::free(self.ptr as *mut c_void);
+        }
+    }
+}
+
+// The buffer is exclusively owned, like a `Box<[u8]>`
+unsafe impl Send for SeqBuf {}
+unsafe impl Sync for SeqBuf {}
+
+/// Fetch and compare the same region from two references with identical coordinates
+///
+/// This is a minimal liftover for assemblies that share a coordinate system,
+/// useful for checking whether two references agree at a locus. Bases are
+/// compared case-insensitively as in [`FastaReader::count_mismatches`], with
+/// `N` matching only `N`; if one reference is shorter, the missing positions
+/// count as mismatches.
+///
+/// # Arguments
+///
+/// * `src` - Reader for the source reference
+/// * `dst` - Reader for the destination reference
+/// * `seqname` - Name of the sequence in both references
+/// * `start` - Start position (0-based, inclusive)
+/// * `end` - End position (0-based, exclusive)
+///
+/// # Returns
+///
+/// The `(source, destination, mismatches)` of the region, where the
+/// references agree if `mismatches` is 0, or an error if either cannot be fetched
+pub fn fetch_lifted(
+    src: &FastaReader,
+    dst: &FastaReader,
+    seqname: &str,
+    start: i64,
+    end: i64,
+) -> FastaResult<(String, String, usize)> {
+    let src_seq = src.fetch_seq(seqname, start, end)?;
+    let dst_seq = dst.fetch_seq(seqname, start, end)?;
+    let mismatches = base_mismatches(src_seq.as_bytes(), dst_seq.as_bytes(), false, usize::MAX)
+        + src_seq.len().abs_diff(dst_seq.len());
+    Ok((src_seq, dst_seq, mismatches))
+}
+
+/// Fetch a batch of regions across worker threads sharing one index
+///
+/// The regions are split into contiguous chunks, one per worker, and each
+/// worker fetches its chunk with its own reader via
+/// [`FastaReader::fetch_regions`]. A `threads` value of 0 is treated as 1.
+/// If a worker cannot create its reader, every region of its chunk fails
+/// with [`FastaError::ReaderCreationError`].
+///
+/// # Arguments
+///
+/// * `index` - Shared FASTA index
+/// * `regions` - `(seqname, start, end)` regions, 0-based and half-open
+/// * `threads` - Number of worker threads
+///
+/// # Returns
+///
+/// One result per region, in input order
+pub fn fetch_regions_parallel(
+    index: &FastaIndex,
+    regions: &[(String, i64, i64)],
+    threads: usize,
+) -> Vec<FastaResult<String>> {
+    if regions.is_empty() {
+        return Vec::new();
     }
+    let threads = threads.max(1);
+    let chunk_size = (regions.len() + threads - 1) / threads;
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = regions
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || match FastaReader::new(index) {
+                    Ok(reader) => reader.fetch_regions(chunk),
+                    Err(_) => chunk
+                        .iter()
+                        .map(|_| Err(FastaError::ReaderCreationError))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("fetch worker thread panicked"))
+            .collect()
+    })
 }
 
-impl Drop for FastaReader {
-    fn drop(&mut self) {
-        unsafe {
-            faidx_reader_destroy(self.reader);
+/// Latency percentiles for a set of fetches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    /// Median latency
+    pub p50: Duration,
+    /// 95th percentile latency
+    pub p95: Duration,
+    /// 99th percentile latency
+    pub p99: Duration,
+}
+
+impl LatencyPercentiles {
+    /// Nearest-rank percentiles of ascending-sorted latencies
+    fn from_sorted(latencies: &[Duration]) -> Self {
+        let percentile = |p: usize| {
+            if latencies.is_empty() {
+                Duration::ZERO
+            } else {
+                let rank = (p * latencies.len() + 99) / 100;
+                latencies[rank.clamp(1, latencies.len()) - 1]
+            }
+        };
+        LatencyPercentiles {
+            p50: percentile(50),
+            p95: percentile(95),
+            p99: percentile(99),
         }
     }
 }
 
-unsafe impl Send for FastaReader {}
+/// Results for one worker thread of a [`throughput`] benchmark
+#[derive(Debug, Clone)]
+pub struct ThreadReport {
+    /// Index of the worker thread
+    pub thread_id: usize,
+    /// Number of fetches attempted
+    pub operations: usize,
+    /// Number of fetches returning the expected sequence length
+    pub successes: usize,
+    /// Latency distribution of this thread's fetches
+    pub latency: LatencyPercentiles,
+}
+
+/// Results of a [`throughput`] benchmark
+#[derive(Debug, Clone)]
+pub struct ThroughputReport {
+    /// Per-thread results, ordered by thread id
+    pub threads: Vec<ThreadReport>,
+    /// Wall-clock time for the whole benchmark
+    pub elapsed: Duration,
+    /// Latency distribution across all threads' fetches
+    pub latency: LatencyPercentiles,
+}
+
+impl ThroughputReport {
+    /// Total number of fetches attempted
+    pub fn operations(&self) -> usize {
+        self.threads.iter().map(|t| t.operations).sum()
+    }
+
+    /// Total number of successful fetches
+    pub fn successes(&self) -> usize {
+        self.threads.iter().map(|t| t.successes).sum()
+    }
+
+    /// Successful fetches per second of wall-clock time
+    pub fn rate(&self) -> f64 {
+        self.successes() as f64 / self.elapsed.as_secs_f64()
+    }
+}
+
+/// Benchmark concurrent random access with one reader per thread
+///
+/// Each thread fetches `operations` short (10 bp) regions, cycling through
+/// the sequences in the index, and times every fetch individually.
+///
+/// # Arguments
+///
+/// * `index` - Shared FASTA index
+/// * `threads` - Number of worker threads
+/// * `operations` - Number of fetches per thread
+///
+/// # Returns
+///
+/// Per-thread and overall counts and latency percentiles, or an error if a
+/// reader cannot be created
+pub fn throughput(
+    index: &FastaIndex,
+    threads: usize,
+    operations: usize,
+) -> FastaResult<ThroughputReport> {
+    const REGION_LEN: i64 = 10;
+
+    let sequences: Vec<(String, i64)> = index
+        .sequence_names()
+        .into_iter()
+        .filter_map(|name| {
+            let length = index.sequence_length(&name)?;
+            (length > REGION_LEN).then_some((name, length))
+        })
+        .collect();
+    if sequences.is_empty() {
+        return Err(FastaError::InvalidParameter(format!(
+            "no sequences longer than {} bases to benchmark",
+            REGION_LEN
+        )));
+    }
+
+    let start = std::time::Instant::now();
+    let results: Vec<FastaResult<(ThreadReport, Vec<Duration>)>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|thread_id| {
+                let sequences = &sequences;
+                scope.spawn(move || {
+                    let reader = FastaReader::new(index)?;
+                    let mut latencies = Vec::with_capacity(operations);
+                    let mut successes = 0;
+
+                    for i in 0..operations {
+                        let (name, length) = &sequences[i % sequences.len()];
+                        let region_start = (i as i64) % (length - REGION_LEN);
+
+                        let fetch_start = std::time::Instant::now();
+                        let result =
+                            reader.fetch_seq(name, region_start, region_start + REGION_LEN);
+                        latencies.push(fetch_start.elapsed());
+
+                        if result.is_ok_and(|seq| seq.len() as i64 == REGION_LEN) {
+                            successes += 1;
+                        }
+                    }
+
+                    latencies.sort_unstable();
+                    let report = ThreadReport {
+                        thread_id,
+                        operations,
+                        successes,
+                        latency: LatencyPercentiles::from_sorted(&latencies),
+                    };
+                    Ok((report, latencies))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("benchmark thread panicked"))
+            .collect()
+    });
+    let elapsed = start.elapsed();
+
+    let mut reports = Vec::with_capacity(threads);
+    let mut all_latencies = Vec::with_capacity(threads * operations);
+    for result in results {
+        let (report, latencies) = result?;
+        reports.push(report);
+        all_latencies.extend(latencies);
+    }
+    all_latencies.sort_unstable();
+
+    Ok(ThroughputReport {
+        threads: reports,
+        elapsed,
+        latency: LatencyPercentiles::from_sorted(&all_latencies),
+    })
+}
+
+/// Write one FASTA record, wrapping the sequence into lines
+///
+/// Emits `>name` followed by the sequence in lines of at most `line_width`
+/// bases. An empty sequence produces only the header line, and a
+/// `line_width` of 0 writes the whole sequence on a single line.
+///
+/// # Arguments
+///
+/// * `w` - Destination for the record
+/// * `name` - Header text written after `>`
+/// * `seq` - Sequence bytes
+/// * `line_width` - Bases per line, or 0 for no wrapping
+pub fn write_fasta_record<W: std::io::Write>(
+    w: &mut W,
+    name: &str,
+    seq: &[u8],
+    line_width: usize,
+) -> std::io::Result<()> {
+    writeln!(w, ">{}", name)?;
+    if seq.is_empty() {
+        return Ok(());
+    }
+    let width = if line_width == 0 {
+        seq.len()
+    } else {
+        line_width
+    };
+    for line in seq.chunks(width) {
+        w.write_all(line)?;
+        w.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Check that a FASTQ region has one quality per base
+fn check_quality_length(sequence: &str, quality: &str) -> FastaResult<()> {
+    if sequence.len() == quality.len() {
+        return Ok(());
+    }
+    Err(FastaError::LengthMismatch {
+        seq_len: sequence.len(),
+        qual_len: quality.len(),
+    })
+}
+
+/// Infer the quality encoding from the ASCII range of sampled quality characters
+fn infer_quality_encoding(quals: &[u8]) -> QualityEncoding {
+    let (Some(&min), Some(&max)) = (quals.iter().min(), quals.iter().max()) else {
+        return QualityEncoding::Ambiguous;
+    };
+
+    if min < b';' {
+        QualityEncoding::Phred33
+    } else if min >= b'@' && max > b'J' {
+        QualityEncoding::Phred64
+    } else {
+        QualityEncoding::Ambiguous
+    }
+}
+
+/// Decode quality characters with the given ASCII offset into Phred scores
+///
+/// `start` is the sequence position of the first character, for error reporting.
+fn phred_scores(quals: &[u8], offset: u8, start: i64) -> FastaResult<Vec<u8>> {
+    const MAX_PHRED: u8 = 93;
+
+    quals
+        .iter()
+        .enumerate()
+        .map(|(i, &qual)| match qual.checked_sub(offset) {
+            Some(score) if score <= MAX_PHRED => Ok(score),
+            _ => Err(FastaError::InvalidQuality(qual as char, start + i as i64)),
+        })
+        .collect()
+}
+
+/// Arithmetic mean of Phred scores, or 0 if there are none
+fn mean_score(scores: &[u8]) -> f64 {
+    if scores.is_empty() {
+        return 0.0;
+    }
+    let sum: u64 = scores.iter().map(|&score| score as u64).sum();
+    sum as f64 / scores.len() as f64
+}
+
+/// Split a region string into sequence name and coordinate range
+///
+/// Returns `None` when the whole string should be treated as a sequence name:
+/// it names a known sequence, has no colon, or the part after the last colon
+/// neither looks like a coordinate range nor follows a known sequence name.
+fn split_region(region: &str, has_sequence: impl Fn(&str) -> bool) -> Option<(&str, &str)> {
+    if has_sequence(region) {
+        return None;
+    }
+    let (seqname, range_part) = region.rsplit_once(':')?;
+
+    let looks_like_range = range_part.bytes().any(|b| b.is_ascii_digit())
+        && range_part.matches('-').count() <= 1
+        && range_part
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b == b',' || b == b'-' || b == b' ');
+    if looks_like_range || has_sequence(seqname) {
+        Some((seqname, range_part))
+    } else {
+        None
+    }
+}
+
+/// Match `text` against a glob `pattern` supporting `*` and `?`
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position after the most recent `*` and the text position it resumes from
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Streaming MD5 (RFC 1321) for sequence digests
+struct Md5 {
+    state: [u32; 4],
+    buffer: [u8; 64],
+    buffered: usize,
+    length: u64,
+}
+
+impl Md5 {
+    const S: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5,
+        9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10,
+        15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+
+    /// `floor(|sin(i + 1)| * 2^32)` for each round `i`
+    const K: [u32; 64] = [
+        0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613,
+        0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193,
+        0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d,
+        0x02441453, 0xd8a1e681, 0xe7d3fbc8, 0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+        0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122,
+        0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+        0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665, 0xf4292244,
+        0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+        0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb,
+        0xeb86d391,
+    ];
+
+    fn new() -> Self {
+        Md5 {
+            state: [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476],
+            buffer: [0; 64],
+            buffered: 0,
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        if self.buffered > 0 {
+            let take = (64 - self.buffered).min(data.len());
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffered = 0;
+        }
+        let mut chunks = data.chunks_exact(64);
+        for block in &mut chunks {
+            self.compress(block);
+        }
+        let remainder = chunks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffered = remainder.len();
+    }
+
+    fn hex_digest(mut self) -> String {
+        let bit_length = self.length.wrapping_mul(8);
+        let mut padding = [0u8; 72];
+        padding[0] = 0x80;
+        let zeros = (119 - self.length as usize % 64) % 64;
+        padding[1 + zeros..9 + zeros].copy_from_slice(&bit_length.to_le_bytes());
+        self.update(&padding[..9 + zeros]);
+
+        self.state
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut m = [0u32; 16];
+        for (word, bytes) in m.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        let [mut a, mut b, mut c, mut d] = self.state;
+
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(Self::K[i])
+                .wrapping_add(m[g])
+                .rotate_left(Self::S[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+}
+
+// Amino acids indexed by codon in TCAG order, as in the NCBI tables
+const STANDARD_CODE: &[u8; 64] =
+    b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG";
+
+/// Look up an NCBI genetic code by its translation table number
+fn genetic_code(table: u8) -> Option<&'static [u8; 64]> {
+    match table {
+        1 | 11 => Some(STANDARD_CODE),
+        // Vertebrate mitochondrial
+        2 => Some(b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG"),
+        // Yeast mitochondrial
+        3 => Some(b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        // Mold, protozoan and coelenterate mitochondrial; Mycoplasma
+        4 => Some(b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG"),
+        // Invertebrate mitochondrial
+        5 => Some(b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG"),
+        _ => None,
+    }
+}
+
+/// Translate a codon with the given genetic code (`X` if any base is ambiguous)
+fn translate_codon(codon: [u8; 3], code: &[u8; 64]) -> u8 {
+    let mut index = 0;
+    for base in codon {
+        let code = match base.to_ascii_uppercase() {
+            b'T' | b'U' => 0,
+            b'C' => 1,
+            b'A' => 2,
+            b'G' => 3,
+            _ => return b'X',
+        };
+        index = index * 4 + code;
+    }
+    code[index]
+}
+
+/// Incremental translation of a base stream in its three reading frames
+#[derive(Default)]
+struct FrameTranslator {
+    proteins: [String; 3],
+    // Bases accumulated towards the next codon of each frame
+    pending: [Vec<u8>; 3],
+    position: usize,
+}
+
+impl FrameTranslator {
+    fn extend(&mut self, bases: impl Iterator<Item = u8>) {
+        for base in bases {
+            for frame in 0..3.min(self.position + 1) {
+                let pending = &mut self.pending[frame];
+                pending.push(base);
+                if pending.len() == 3 {
+                    let amino_acid =
+                        translate_codon([pending[0], pending[1], pending[2]], STANDARD_CODE);
+                    self.proteins[frame].push(amino_acid as char);
+                    pending.clear();
+                }
+            }
+            self.position += 1;
+        }
+    }
+}
+
+/// Case-insensitive mismatches between paired bases, stopping once more than `limit` are found
+fn base_mismatches(a: &[u8], b: &[u8], n_is_wildcard: bool, limit: usize) -> usize {
+    let mut mismatches = 0;
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (x.to_ascii_uppercase(), y.to_ascii_uppercase());
+        if x != y && !(n_is_wildcard && (x == b'N' || y == b'N')) {
+            mismatches += 1;
+            if mismatches > limit {
+                break;
+            }
+        }
+    }
+    mismatches
+}
+
+/// Start offsets of the (possibly overlapping) matches of an IUPAC motif in `seq`
+fn motif_matches<'a>(seq: &'a [u8], motif: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    seq.windows(motif.len())
+        .enumerate()
+        .filter(move |(_, window)| window.iter().zip(motif).all(|(&b, &m)| iupac_matches(m, b)))
+        .map(|(i, _)| i)
+}
+
+/// Whether `base` is one of the bases represented by the IUPAC `code` (case-insensitive)
+fn iupac_matches(code: u8, base: u8) -> bool {
+    let (code, base) = (code.to_ascii_uppercase(), base.to_ascii_uppercase());
+    if code == base {
+        return true;
+    }
+    let bases: &[u8] = match code {
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"CG",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        b'N' => b"ACGT",
+        _ => b"",
+    };
+    bases.contains(&base)
+}
+
+/// Complement a single IUPAC nucleotide code, preserving case
+fn complement(base: u8) -> u8 {
+    let comp = match base.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'T' | b'U' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other, // S, W, N and non-nucleotides are unchanged
+    };
+    if base.is_ascii_lowercase() {
+        comp.to_ascii_lowercase()
+    } else {
+        comp
+    }
+}
+
+/// Reverse-complement a nucleotide sequence, preserving case
+fn reverse_complement(seq: &str) -> String {
+    seq.bytes().rev().map(|b| complement(b) as char).collect()
+}
+
+/// Map a nucleotide to its 2-bit code, or `None` for anything but A/C/G/T
+fn nt_to_2bit(base: u8) -> Option<u64> {
+    match base {
+        b'A' | b'a' => Some(0),
+        b'C' | b'c' => Some(1),
+        b'G' | b'g' => Some(2),
+        b'T' | b't' => Some(3),
+        _ => None,
+    }
+}
+
+/// Invertible integer hash over `mask` bits (Thomas Wang's 64-bit mix, as used by minimap2)
+fn hash64(key: u64, mask: u64) -> u64 {
+    let mut key = (!key).wrapping_add(key << 21) & mask;
+    key ^= key >> 24;
+    key = key.wrapping_add(key << 3).wrapping_add(key << 8) & mask;
+    key ^= key >> 14;
+    key = key.wrapping_add(key << 2).wrapping_add(key << 4) & mask;
+    key ^= key >> 28;
+    key = key.wrapping_add(key << 31) & mask;
+    key
+}
+
+/// Windowed-minimum sketch over canonical k-mers, returning `(hash, offset)` pairs
+fn minimizer_sketch(seq: &[u8], k: usize, w: usize) -> Vec<(u64, usize)> {
+    use std::collections::VecDeque;
+
+    let mask = if k == 32 {
+        u64::MAX
+    } else {
+        (1u64 << (2 * k)) - 1
+    };
+    let shift = 2 * (k as u64 - 1);
+
+    let mut sketch: Vec<(u64, usize)> = Vec::new();
+    // Monotonic queue of (hash, k-mer index, offset); the front is the window minimum
+    let mut window: VecDeque<(u64, usize, usize)> = VecDeque::new();
+    let mut fwd = 0u64;
+    let mut rev = 0u64;
+    let mut valid = 0usize; // consecutive valid bases
+    let mut kmer_idx = 0usize; // k-mers seen since the last ambiguous base
+
+    for (i, &base) in seq.iter().enumerate() {
+        let code = match nt_to_2bit(base) {
+            Some(code) => code,
+            None => {
+                valid = 0;
+                kmer_idx = 0;
+                window.clear();
+                continue;
+            }
+        };
+
+        fwd = ((fwd << 2) | code) & mask;
+        rev = (rev >> 2) | ((3 - code) << shift);
+        valid += 1;
+        if valid < k {
+            continue;
+        }
+
+        let hash = hash64(fwd.min(rev), mask);
+        let pos = i + 1 - k;
+
+        // Keep earlier k-mers with an equal hash so ties resolve leftmost
+        while window.back().is_some_and(|&(h, _, _)| h > hash) {
+            window.pop_back();
+        }
+        window.push_back((hash, kmer_idx, pos));
+        while window
+            .front()
+            .is_some_and(|&(_, idx, _)| idx + w <= kmer_idx)
+        {
+            window.pop_front();
+        }
+        kmer_idx += 1;
+
+        if kmer_idx >= w {
+            let &(min_hash, _, min_pos) = window.front().unwrap();
+            if sketch.last() != Some(&(min_hash, min_pos)) {
+                sketch.push((min_hash, min_pos));
+            }
+        }
+    }
+
+    sketch
+}
 
 #[cfg(test)]
 mod tests {
@@ -387,14 +5053,422 @@ mod tests {
         assert!(index.num_sequences() > 0);
     }
 
+    #[test]
+    fn test_minimizer_sketch() {
+        // A window of one k-mer selects every valid k-mer
+        let all = minimizer_sketch(b"ACGTAC", 3, 1);
+        assert_eq!(
+            all.iter().map(|&(_, pos)| pos).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+
+        // Canonical k-mers hash identically on both strands
+        let fwd = minimizer_sketch(b"AAC", 3, 1);
+        let rev = minimizer_sketch(b"GTT", 3, 1);
+        assert_eq!(fwd[0].0, rev[0].0);
+
+        // Ambiguous bases break k-mers and restart the window
+        let gapped = minimizer_sketch(b"ACGNNACG", 3, 1);
+        assert_eq!(
+            gapped.iter().map(|&(_, pos)| pos).collect::<Vec<_>>(),
+            vec![0, 5]
+        );
+
+        // Identical k-mers tie and resolve to the leftmost position
+        let ties = minimizer_sketch(b"AAAAAA", 3, 4);
+        assert_eq!(ties.len(), 1);
+        assert_eq!(ties[0].1, 0);
+    }
+
+    #[test]
+    fn test_infer_quality_encoding() {
+        assert_eq!(infer_quality_encoding(b"!#5?IIJ"), QualityEncoding::Phred33);
+        assert_eq!(infer_quality_encoding(b"@BPhhf"), QualityEncoding::Phred64);
+        assert_eq!(infer_quality_encoding(b"@@IIJ"), QualityEncoding::Ambiguous);
+        assert_eq!(infer_quality_encoding(b""), QualityEncoding::Ambiguous);
+    }
+
+    #[test]
+    fn test_write_fasta_record() {
+        let record = |seq: &[u8], line_width| {
+            let mut out = Vec::new();
+            write_fasta_record(&mut out, "chr1 desc", seq, line_width).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(record(b"ACGTACGTAC", 4), ">chr1 desc\nACGT\nACGT\nAC\n");
+        assert_eq!(record(b"ACGTACGT", 4), ">chr1 desc\nACGT\nACGT\n");
+        assert_eq!(record(b"ACGTACGT", 0), ">chr1 desc\nACGTACGT\n");
+        assert_eq!(record(b"", 4), ">chr1 desc\n");
+        assert_eq!(record(b"", 0), ">chr1 desc\n");
+    }
+
+    #[test]
+    fn test_fasta_record_strings() {
+        let record = FastaRecord {
+            name: "read1".to_string(),
+            seq: "ACGTA".to_string(),
+            qual: Some("IIIII".to_string()),
+        };
+        assert_eq!(record.to_fasta_string(2), ">read1\nAC\nGT\nA\n");
+        assert_eq!(record.to_fasta_string(0), ">read1\nACGTA\n");
+        assert_eq!(record.to_string(), record.to_fasta_string(0));
+        assert_eq!(record.to_fastq_string(), "@read1\nACGTA\n+\nIIIII\n");
+
+        let unqualified = FastaRecord {
+            qual: None,
+            ..record
+        };
+        assert_eq!(unqualified.to_fastq_string(), "@read1\nACGTA\n+\n!!!!!\n");
+    }
+
+    #[test]
+    fn test_reverse_complement() {
+        assert_eq!(reverse_complement("ACGTN"), "NACGT");
+        assert_eq!(reverse_complement("aacgRY"), "RYcgtt");
+        assert_eq!(reverse_complement(""), "");
+    }
+
+    #[test]
+    fn test_error_retryable() {
+        assert!(FastaError::IoError("connection reset".to_string()).is_retryable());
+        assert!(!FastaError::SequenceNotFound("chr1".to_string()).is_retryable());
+        assert!(!FastaError::InvalidRegion("chr1:10-5".to_string()).is_retryable());
+        let invalid = FastaError::InvalidCoordinates {
+            seqname: "chr1".to_string(),
+            start: 10,
+            end: 5,
+        };
+        assert!(!invalid.is_retryable());
+        assert_eq!(invalid.to_string(), "Invalid region: chr1:10-5");
+        assert!(!FastaError::IndexLoadError("genome.fa".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_check_quality_length() {
+        assert!(check_quality_length("ACGT", "IIII").is_ok());
+        assert!(check_quality_length("", "").is_ok());
+
+        let err = check_quality_length("ACGT", "III").unwrap_err();
+        assert!(matches!(
+            err,
+            FastaError::LengthMismatch {
+                seq_len: 4,
+                qual_len: 3
+            }
+        ));
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "Sequence and quality lengths differ: 4 bases, 3 qualities"
+        );
+    }
+
+    #[test]
+    fn test_pending_fetch_limit() {
+        let pending = Arc::new(AtomicUsize::new(0));
+        let mut slots: Vec<PendingFetch> = (0..MAX_PENDING_FETCHES)
+            .map(|_| PendingFetch::acquire(&pending).unwrap())
+            .collect();
+        assert!(PendingFetch::acquire(&pending).is_none());
+
+        // A finished helper frees its slot
+        slots.pop();
+        assert_eq!(pending.load(Ordering::Acquire), MAX_PENDING_FETCHES - 1);
+        assert!(PendingFetch::acquire(&pending).is_some());
+        drop(slots);
+        assert_eq!(pending.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn test_io_error_source() {
+        use std::error::Error as _;
+
+        let err = FastaIndex::new_auto("/nonexistent/genome.fa").unwrap_err();
+        // A missing file stays missing
+        assert!(!err.is_retryable());
+        let interrupted = FastaError::Io {
+            context: "genome.fa".to_string(),
+            source: std::io::Error::from(std::io::ErrorKind::Interrupted),
+        };
+        assert!(interrupted.is_retryable());
+        let source = err.source().expect("I/O errors keep their source");
+        let io = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        assert!(err
+            .to_string()
+            .starts_with("I/O error: /nonexistent/genome.fa: "));
+    }
+
+    #[test]
+    fn test_bed_parse_line() {
+        let record = BedRecord::parse_line("chr1\t100\t200\tgene1\t0\t-\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.chrom, "chr1");
+        assert_eq!((record.start, record.end), (100, 200));
+        assert_eq!(record.name.as_deref(), Some("gene1"));
+        assert_eq!(record.strand, '-');
+
+        let minimal = BedRecord::parse_line("chr2\t5\t10").unwrap().unwrap();
+        assert_eq!(minimal.name, None);
+        assert_eq!(minimal.strand, '.');
+
+        assert!(BedRecord::parse_line("# comment").is_none());
+        assert!(BedRecord::parse_line("track name=test").is_none());
+        assert!(BedRecord::parse_line("").is_none());
+        assert!(BedRecord::parse_line("chr1\t100").unwrap().is_err());
+        assert!(BedRecord::parse_line("chr1\tx\t200").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"chr*", b"chr1"));
+        assert!(glob_match(b"chr*", b"chr"));
+        assert!(glob_match(b"*_random", b"chrUn_random"));
+        assert!(glob_match(b"chr?", b"chrX"));
+        assert!(glob_match(b"*#chr*I", b"SGDref#1#chrIII"));
+        assert!(!glob_match(b"chr?", b"chr10"));
+        assert!(!glob_match(b"chr*", b"scaffold1"));
+        assert!(!glob_match(b"chr1", b"chr10"));
+    }
+
+    #[test]
+    fn test_base_counts_tally() {
+        let mut counts = BaseCounts::default();
+        counts.tally(b"ACGTacgtNNnRu", true);
+        assert_eq!(
+            counts.a,
+            BaseCount {
+                unmasked: 1,
+                masked: 1
+            }
+        );
+        assert_eq!(
+            counts.t,
+            BaseCount {
+                unmasked: 1,
+                masked: 2
+            }
+        );
+        assert_eq!(
+            counts.n,
+            BaseCount {
+                unmasked: 2,
+                masked: 1
+            }
+        );
+        assert_eq!(counts.other.total(), 1);
+        assert_eq!(counts.total(), 13);
+        assert_eq!(counts.masked(), 6);
+
+        let mut unsplit = BaseCounts::default();
+        unsplit.tally(b"acgt", false);
+        assert_eq!(unsplit.masked(), 0);
+        assert_eq!(unsplit.masked_fraction(), 0.0);
+
+        let mut gc = BaseCounts::default();
+        assert_eq!(gc.gc_fraction(), 0.0);
+        gc.tally(b"GCatNNRRs", true);
+        assert_eq!(gc.s.total(), 1);
+        assert_eq!(gc.gc_fraction(), 3.0 / 7.0);
+    }
+
+    #[test]
+    fn test_md5() {
+        let digest = |data: &[u8]| {
+            let mut hasher = Md5::new();
+            hasher.update(data);
+            hasher.hex_digest()
+        };
+        assert_eq!(digest(b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(digest(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        // Padding spills into a second block
+        assert_eq!(digest(&[b'A'; 60]), "e009747e74dd24f3274fc71c240921b7");
+        assert_eq!(
+            digest(
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            ),
+            "57edf4a22be3c955ac49da2e2107b67a"
+        );
+
+        // Streaming in uneven pieces matches a single update
+        let mut hasher = Md5::new();
+        for piece in [&b"ACGT"[..], &[b'A'; 70], b"TTG"] {
+            hasher.update(piece);
+        }
+        let mut whole = b"ACGT".to_vec();
+        whole.extend_from_slice(&[b'A'; 70]);
+        whole.extend_from_slice(b"TTG");
+        assert_eq!(hasher.hex_digest(), digest(&whole));
+    }
+
+    #[test]
+    fn test_iupac_matches() {
+        assert!(iupac_matches(b'A', b'a'));
+        assert!(iupac_matches(b'N', b'G'));
+        assert!(iupac_matches(b'R', b'g'));
+        assert!(!iupac_matches(b'R', b'C'));
+        assert!(!iupac_matches(b'A', b'N'));
+        assert!(iupac_matches(b'N', b'N'));
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let latencies: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+        let percentiles = LatencyPercentiles::from_sorted(&latencies);
+        assert_eq!(percentiles.p50, Duration::from_millis(50));
+        assert_eq!(percentiles.p95, Duration::from_millis(95));
+        assert_eq!(percentiles.p99, Duration::from_millis(99));
+
+        let single = LatencyPercentiles::from_sorted(&[Duration::from_millis(7)]);
+        assert_eq!(single.p50, Duration::from_millis(7));
+        assert_eq!(single.p99, Duration::from_millis(7));
+
+        assert_eq!(
+            LatencyPercentiles::from_sorted(&[]),
+            LatencyPercentiles::default()
+        );
+    }
+
+    #[test]
+    fn test_frame_translation() {
+        assert_eq!(translate_codon(*b"ATG", STANDARD_CODE), b'M');
+        assert_eq!(translate_codon(*b"taa", STANDARD_CODE), b'*');
+        assert_eq!(translate_codon(*b"GGN", STANDARD_CODE), b'X');
+
+        let mito = genetic_code(2).unwrap();
+        assert_eq!(translate_codon(*b"TGA", mito), b'W');
+        assert_eq!(translate_codon(*b"AGA", mito), b'*');
+        assert_eq!(translate_codon(*b"ATA", mito), b'M');
+        assert_eq!(genetic_code(11), Some(STANDARD_CODE));
+        assert_eq!(genetic_code(99), None);
+
+        let mut frames = FrameTranslator::default();
+        frames.extend(b"ATGGCC".iter().copied());
+        frames.extend(b"TAAG".iter().copied());
+        assert_eq!(frames.proteins[0], "MA*");
+        assert_eq!(frames.proteins[1], "WPK"); // TGG CCT AAG
+        assert_eq!(frames.proteins[2], "GL"); // GGC CTA AG-
+    }
+
+    #[test]
+    fn test_phred_scores() {
+        assert_eq!(
+            phred_scores(b"!+5I~", 33, 0).unwrap(),
+            vec![0, 10, 20, 40, 93]
+        );
+        assert_eq!(phred_scores(b"@Jh", 64, 0).unwrap(), vec![0, 10, 40]);
+
+        match phred_scores(b"II I", 33, 100) {
+            Err(FastaError::InvalidQuality(' ', 102)) => (),
+            other => panic!("Expected InvalidQuality, got {:?}", other),
+        }
+        assert!(phred_scores(b"5", 64, 0).is_err());
+
+        assert_eq!(mean_score(&[10, 20, 40]), 70.0 / 3.0);
+        assert_eq!(mean_score(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_split_region() {
+        let names = ["chr1", "HLA:A*01:01", "HLA:B*07"];
+        let has_sequence = |name: &str| names.contains(&name);
+
+        assert_eq!(
+            split_region("chr1:1,000-2,000", has_sequence),
+            Some(("chr1", "1,000-2,000"))
+        );
+        assert_eq!(split_region("chr1", has_sequence), None);
+
+        // Names with embedded colons split at the last colon only
+        assert_eq!(split_region("HLA:A*01:01", has_sequence), None);
+        assert_eq!(
+            split_region("HLA:A*01:01:100-200", has_sequence),
+            Some(("HLA:A*01:01", "100-200"))
+        );
+        assert_eq!(
+            split_region("HLA:B*07:5-", has_sequence),
+            Some(("HLA:B*07", "5-"))
+        );
+        assert_eq!(split_region("HLA:C*02", has_sequence), None);
+
+        // Malformed ranges of known sequences are still split, to be rejected
+        assert_eq!(
+            split_region("chr1:abc", has_sequence),
+            Some(("chr1", "abc"))
+        );
+    }
+
+    #[test]
+    fn test_region_parse() {
+        let region = |name: &str, start, end| Region {
+            name: name.to_string(),
+            start,
+            end,
+        };
+
+        assert_eq!(
+            Region::parse("chr1:1,001-2,000").unwrap(),
+            region("chr1", Some(1001), Some(2000))
+        );
+        assert_eq!(
+            Region::parse("chr1: 10 - 20 ").unwrap(),
+            region("chr1", Some(10), Some(20))
+        );
+        assert_eq!(
+            Region::parse("chr1:500").unwrap(),
+            region("chr1", Some(500), None)
+        );
+        assert_eq!(
+            Region::parse("chr1:500-").unwrap(),
+            region("chr1", Some(500), None)
+        );
+        assert_eq!(
+            Region::parse("chr1:-500").unwrap(),
+            region("chr1", None, Some(500))
+        );
+        assert_eq!(Region::parse("chr1").unwrap(), region("chr1", None, None));
+
+        for malformed in ["", ":1-10"] {
+            assert!(matches!(
+                Region::parse(malformed),
+                Err(FastaError::InvalidRegion(_))
+            ));
+        }
+        // Without an index, a suffix that is not a range is part of the name
+        assert_eq!(
+            Region::parse("HLA:A*01").unwrap(),
+            region("HLA:A*01", None, None)
+        );
+
+        assert_eq!(region("chr1", Some(11), Some(20)).bounds(100), (10, 20));
+        assert_eq!(region("chr1", None, None).bounds(100), (0, 100));
+
+        for text in ["chr1:1001-2000", "chr1:500-", "chr1:-500", "chr1"] {
+            assert_eq!(Region::parse(text).unwrap().to_string(), text);
+        }
+    }
+
     #[test]
     fn test_error_handling() {
         let result = FastaIndex::new("/nonexistent/file.fa", FastaFormat::Fasta);
         assert!(result.is_err());
 
         match result.unwrap_err() {
-            FastaError::IndexLoadError(_) => (),
-            _ => panic!("Expected IndexLoadError"),
+            FastaError::Io { .. } => (),
+            e => panic!("Expected Io, got {:?}", e),
+        }
+    }
+
+    #[test]
+    fn test_index_load_error_reason() {
+        let missing = std::fs::File::open("/nonexistent/file.fa.fai").unwrap_err();
+        let err = FastaIndex::new("/nonexistent/file.fa", FastaFormat::Fasta).unwrap_err();
+        assert!(err.to_string().contains(&missing.to_string()), "{}", err);
+        assert!(err.to_string().contains("samtools faidx"));
+        match err {
+            FastaError::Io { source, .. } => assert_eq!(source.kind(), missing.kind()),
+            e => panic!("Expected Io, got {:?}", e),
         }
     }
 }
@@ -1,4 +1,6 @@
-use faigz_rs::{FastaError, FastaFormat, FastaIndex, FastaReader};
+use faigz_rs::{
+    fetch_lifted, Anchor, FastaError, FastaFormat, FastaIndex, FastaReader, IndexCreation,
+};
 use std::io::Write;
 use std::sync::Arc;
 use std::thread;
@@ -100,8 +102,8 @@ fn test_error_handling() {
     assert!(result.is_err());
 
     match result.unwrap_err() {
-        FastaError::IndexLoadError(_) => (),
-        _ => panic!("Expected IndexLoadError"),
+        FastaError::Io { source, .. } => assert_eq!(source.kind(), std::io::ErrorKind::NotFound),
+        e => panic!("Expected Io, got {:?}", e),
     }
 
     // Test with empty path
@@ -109,6 +111,557 @@ fn test_error_handling() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_inconsistent_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.fa");
+    std::fs::write(&path, ">seq1\nATCGATCGATCGATCG\n").unwrap();
+
+    // seq2 is listed in the .fai but its data lies past the end of the file
+    std::fs::write(
+        dir.path().join("test.fa.fai"),
+        "seq1\t16\t6\t16\t17\nseq2\t16\t40\t16\t17\n",
+    )
+    .unwrap();
+
+    // The check is opt-in, so a plain load does not read the FASTA
+    assert!(FastaIndex::new(path.to_str().unwrap(), FastaFormat::Fasta).is_ok());
+
+    let result = FastaIndex::builder()
+        .validate_offsets(true)
+        .open(path.to_str().unwrap());
+    match result.unwrap_err() {
+        FastaError::InconsistentIndex(msg) => assert!(msg.contains("seq2")),
+        e => panic!("Expected InconsistentIndex, got {:?}", e),
+    }
+}
+
+#[test]
+fn test_open_existing_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("test.fa");
+    let path_str = path.to_str().unwrap();
+
+    match FastaIndex::open(path_str, FastaFormat::Fasta).unwrap_err() {
+        FastaError::Io { context, source } => {
+            assert!(context.contains("FASTA file"));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        e => panic!("Expected Io, got {:?}", e),
+    }
+
+    std::fs::write(&path, ">seq1\nATCGATCGATCGATCG\n").unwrap();
+    match FastaIndex::open(path_str, FastaFormat::Fasta).unwrap_err() {
+        FastaError::Io { context, source } => {
+            assert!(context.contains(".fai"));
+            assert!(context.contains("samtools faidx"));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        e => panic!("Expected Io, got {:?}", e),
+    }
+    // No index is created as a side effect
+    assert!(!dir.path().join("test.fa.fai").exists());
+
+    std::fs::write(dir.path().join("test.fa.fai"), "seq1\t16\t6\t16\t17\n").unwrap();
+    let index = FastaIndex::open(path_str, FastaFormat::Fasta).unwrap();
+    assert_eq!(index.sequence_length("seq1"), Some(16));
+}
+
+#[test]
+fn test_new_with_index() {
+    let fasta_dir = tempfile::tempdir().unwrap();
+    let index_dir = tempfile::tempdir().unwrap();
+    let fasta_path = fasta_dir.path().join("test.fa");
+    let fai_path = index_dir.path().join("custom.fai");
+    let fasta = fasta_path.to_str().unwrap();
+    let fai = fai_path.to_str().unwrap();
+
+    match FastaIndex::new_with_index(fasta, fai, FastaFormat::Fasta).unwrap_err() {
+        FastaError::InvalidPath(path) => assert_eq!(path, fasta),
+        e => panic!("Expected InvalidPath, got {:?}", e),
+    }
+
+    std::fs::write(&fasta_path, ">seq1\nATCGATCGATCGATCG\n").unwrap();
+    match FastaIndex::new_with_index(fasta, fai, FastaFormat::Fasta).unwrap_err() {
+        FastaError::Io { context, source } => {
+            assert!(context.contains(fai));
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        e => panic!("Expected Io, got {:?}", e),
+    }
+
+    std::fs::write(&fai_path, "seq1\t16\t6\t16\t17\n").unwrap();
+    let index = FastaIndex::new_with_index(fasta, fai, FastaFormat::Fasta).unwrap();
+    assert_eq!(index.sequence_length("seq1"), Some(16));
+}
+
+#[test]
+fn test_builder_create() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nATCGATCGATCGATCG\n>seq2\nGCTA\n").unwrap();
+    let path = fasta.to_str().unwrap();
+
+    // Read-only by default: a missing index is an error, not created
+    match FastaIndex::builder().open(path).unwrap_err() {
+        FastaError::Io { source, .. } => assert_eq!(source.kind(), std::io::ErrorKind::NotFound),
+        e => panic!("Expected Io, got {:?}", e),
+    }
+    assert!(!dir.path().join("test.fa.fai").exists());
+
+    let custom = dir.path().join("custom.fai");
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .index_path(Some(custom.clone()))
+        .open(path)
+        .unwrap();
+    assert!(custom.is_file());
+    assert!(!dir.path().join("test.fa.fai").exists());
+    assert_eq!(index.sequence_names(), vec!["seq1", "seq2"]);
+    assert_eq!(index.sequence_length("seq1"), Some(16));
+
+    // An existing index is reused without creation
+    let index = FastaIndex::builder()
+        .index_path(Some(custom.clone()))
+        .open(path)
+        .unwrap();
+    assert_eq!(index.sequence_length("seq2"), Some(4));
+
+    // A stale index is kept when missing ones are created, and rebuilt on demand
+    std::fs::write(&fasta, ">seq1\nATCG\n>seq2\nGCTAGCTA\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .index_path(Some(custom.clone()))
+        .open(path)
+        .unwrap();
+    assert_eq!(index.sequence_length("seq2"), Some(4));
+    let index = FastaIndex::builder()
+        .create(IndexCreation::Always)
+        .index_path(Some(custom))
+        .open(path)
+        .unwrap();
+    assert_eq!(index.sequence_length("seq1"), Some(4));
+    assert_eq!(index.sequence_length("seq2"), Some(8));
+
+    let fastq = dir.path().join("test.fq");
+    std::fs::write(&fastq, "@seq1\nACGT\n+\nIIII\n").unwrap();
+    match FastaIndex::builder()
+        .format(FastaFormat::Fastq)
+        .create(IndexCreation::IfMissing)
+        .open(fastq.to_str().unwrap())
+        .unwrap_err()
+    {
+        FastaError::IndexLoadError(msg) => assert!(msg.contains("samtools faidx")),
+        e => panic!("Expected IndexLoadError, got {:?}", e),
+    }
+}
+
+#[test]
+fn test_builder_mmap() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(
+        &fasta,
+        ">seq1\nATCGATCGAT\nCGATCGAT\n>seq2\nGGGGCCCCAA\nAATTTT\n",
+    )
+    .unwrap();
+    let path = fasta.to_str().unwrap();
+
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(path)
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    assert_eq!(reader.fetch_seq_all("seq1").unwrap(), "ATCGATCGATCGATCGAT");
+    assert_eq!(reader.fetch_seq("seq1", 8, 12).unwrap(), "ATCG");
+    assert_eq!(reader.fetch_seq("seq2", 9, 100).unwrap(), "AAATTTT");
+    assert_eq!(reader.fetch_seq("seq2", 5, 5).unwrap(), "");
+    assert!(matches!(
+        reader.fetch_seq("seq2", 16, 20),
+        Err(FastaError::InvalidCoordinates { .. })
+    ));
+    assert!(matches!(
+        reader.fetch_seq("seq3", 0, 1),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+
+    // Readers of clones share the mapping
+    let reader = FastaReader::new(&index.clone()).unwrap();
+    assert_eq!(reader.fetch_region("seq2:1-4").unwrap(), "GGGG");
+}
+
+#[test]
+fn test_compression_info() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nATCGATCGATCGATCG\n").unwrap();
+    std::fs::write(dir.path().join("test.fa.fai"), "seq1\t16\t6\t16\t17\n").unwrap();
+
+    let index = FastaIndex::new(fasta.to_str().unwrap(), FastaFormat::Fasta).unwrap();
+    assert!(!index.is_compressed());
+    assert!(!index.is_bgzf());
+    assert_eq!(index.gzi_path(), None);
+
+    // A plain gzip header: no FEXTRA field, so no BGZF block size
+    let gzipped = dir.path().join("test.fa.gz");
+    let mut bytes = vec![0x1f, 0x8b, 0x08, 0x00, 0, 0, 0, 0, 0x00, 0x03];
+    bytes.extend_from_slice(&[0; 16]);
+    std::fs::write(&gzipped, bytes).unwrap();
+    std::fs::write(dir.path().join("test.fa.gz.fai"), "seq1\t16\t6\t16\t17\n").unwrap();
+
+    let path = gzipped.to_str().unwrap();
+    match FastaIndex::new(path, FastaFormat::Fasta).unwrap_err() {
+        FastaError::NotBgzf(p) => assert_eq!(p, path),
+        e => panic!("Expected NotBgzf, got {:?}", e),
+    }
+    assert!(matches!(
+        FastaIndex::builder().open(path),
+        Err(FastaError::NotBgzf(_))
+    ));
+}
+
+#[test]
+fn test_resolve_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">chr1\nACGT\n>2\nACGT\n>chrX\nACGT\n>X\nACGT\n").unwrap();
+    std::fs::write(
+        dir.path().join("test.fa.fai"),
+        "chr1\t4\t6\t4\t5\n2\t4\t14\t4\t5\nchrX\t4\t25\t4\t5\nX\t4\t33\t4\t5\n",
+    )
+    .unwrap();
+    let index = FastaIndex::new(fasta.to_str().unwrap(), FastaFormat::Fasta).unwrap();
+
+    assert_eq!(index.resolve_name("chr1").as_deref(), Some("chr1"));
+    assert_eq!(index.resolve_name("1").as_deref(), Some("chr1"));
+    assert_eq!(index.resolve_name("chr2").as_deref(), Some("2"));
+    // An exact match wins over the prefixed variant
+    assert_eq!(index.resolve_name("X").as_deref(), Some("X"));
+    assert_eq!(index.resolve_name("3"), None);
+    assert_eq!(index.resolve_name("chr3"), None);
+
+    // Strict lookups are unaffected
+    assert!(!index.has_sequence("1"));
+}
+
+#[test]
+fn test_kmers() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nACGTNacgt\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    let kmers: Vec<Vec<u8>> = reader.kmers("seq1", 0, 9, 3, false).unwrap().collect();
+    let expected: Vec<&[u8]> = vec![b"ACG", b"CGT", b"GTN", b"TNa", b"Nac", b"acg", b"cgt"];
+    assert_eq!(kmers, expected);
+
+    let kmers: Vec<Vec<u8>> = reader.kmers("seq1", 0, 9, 3, true).unwrap().collect();
+    let expected: Vec<&[u8]> = vec![b"ACG", b"CGT", b"acg", b"cgt"];
+    assert_eq!(kmers, expected);
+
+    assert_eq!(reader.kmers("seq1", 0, 9, 9, false).unwrap().count(), 1);
+    assert_eq!(reader.kmers("seq1", 0, 9, 10, false).unwrap().count(), 0);
+    assert_eq!(reader.kmers("seq1", 2, 2, 1, false).unwrap().count(), 0);
+    assert!(matches!(
+        reader.kmers("seq1", 0, 9, 0, false),
+        Err(FastaError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_masked_intervals() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nacGTAc\ngtACGT\nTTaa\n>seq2\nACGT\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    // Runs continue across line breaks and may end the sequence
+    assert_eq!(
+        reader.masked_intervals("seq1").unwrap(),
+        vec![(0, 2), (5, 8), (14, 16)]
+    );
+    assert!(reader.masked_intervals("seq2").unwrap().is_empty());
+    assert!(matches!(
+        reader.masked_intervals("missing"),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+}
+
+#[test]
+fn test_n_gaps() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nNNACGN\nnNNAcg\ntNNNNN\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    assert_eq!(
+        reader.n_gaps("seq1", 0).unwrap(),
+        vec![(0, 2), (5, 9), (13, 18)]
+    );
+    assert_eq!(reader.n_gaps("seq1", 4).unwrap(), vec![(5, 9), (13, 18)]);
+    assert!(reader.n_gaps("seq1", 6).unwrap().is_empty());
+}
+
+#[test]
+fn test_fetch_bed() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nAACCGGTTAC\n>seq2\nGGGGATATAT\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    let bed = "track name=test\n\
+               # comment\n\
+               seq1\t0\t4\n\
+               browser position seq1\n\
+               seq1\t2\t5\tfeat1\t0\t-\n\
+               seq2\t4\tx\n\
+               seq2\t4\t8\tfeat2\t0\t.\n\
+               missing\t0\t4\n";
+    let results: Vec<_> = reader.fetch_bed(bed.as_bytes()).collect();
+    assert_eq!(results.len(), 5);
+
+    let ok = |i: usize| results[i].as_ref().unwrap().clone();
+    assert_eq!(ok(0), ("seq1:0-4".to_string(), "AACC".to_string()));
+    assert_eq!(ok(1), ("feat1".to_string(), "CGG".to_string()));
+    assert!(matches!(results[2], Err(FastaError::InvalidRegion(_))));
+    assert_eq!(ok(3), ("feat2".to_string(), "ATAT".to_string()));
+    assert!(results[4].is_err());
+}
+
+#[test]
+fn test_write_fasta_from_bed() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nAACCGGTTAC\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    let bed = "seq1\t0\t6\texon1\nseq1\t6\t10\n";
+    let mut out = Vec::new();
+    let written = reader
+        .write_fasta_from_bed(bed.as_bytes(), &mut out, 4)
+        .unwrap();
+    assert_eq!(written, 2);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        ">exon1\nAACC\nGG\n>seq1:6-10\nTTAC\n"
+    );
+
+    let mut out = Vec::new();
+    assert!(reader
+        .write_fasta_from_bed("seq1\t0\tx\n".as_bytes(), &mut out, 4)
+        .is_err());
+}
+
+#[test]
+fn test_fetch_fixed_non_ascii() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nAC\u{e9}GT\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    // The two bytes of "é" are two positions, each kept as a single byte
+    assert_eq!(
+        reader
+            .fetch_fixed("seq1", 0, 6, b'-', Anchor::Left)
+            .unwrap(),
+        "ACNNGT"
+    );
+    assert_eq!(
+        reader
+            .fetch_fixed("seq1", 3, 6, b'-', Anchor::Center)
+            .unwrap(),
+        "ACNNGT"
+    );
+    assert_eq!(
+        reader
+            .fetch_fixed("seq1", 8, 4, b'-', Anchor::Right)
+            .unwrap(),
+        "GT--"
+    );
+}
+
+#[test]
+fn test_fetch_lifted() {
+    let dir = tempfile::tempdir().unwrap();
+    let open = |file: &str, contents: &str| {
+        let fasta = dir.path().join(file);
+        std::fs::write(&fasta, contents).unwrap();
+        FastaIndex::builder()
+            .create(IndexCreation::IfMissing)
+            .mmap(true)
+            .open(fasta.to_str().unwrap())
+            .unwrap()
+    };
+    let src_index = open("src.fa", ">chr1\nACGTACGTAC\n");
+    let dst_index = open("dst.fa", ">chr1\nACGTtCGtac\n");
+    let src = FastaReader::new(&src_index).unwrap();
+    let dst = FastaReader::new(&dst_index).unwrap();
+
+    let (src_seq, dst_seq, mismatches) = fetch_lifted(&src, &dst, "chr1", 2, 8).unwrap();
+    assert_eq!(src_seq, "GTACGT");
+    assert_eq!(dst_seq, "GTtCGt");
+    assert_eq!(mismatches, 1);
+
+    // Case differences alone are not mismatches
+    let (_, _, mismatches) = fetch_lifted(&src, &dst, "chr1", 5, 10).unwrap();
+    assert_eq!(mismatches, 0);
+
+    assert!(matches!(
+        fetch_lifted(&src, &dst, "chr2", 0, 5),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+}
+
+#[test]
+fn test_genome_composition() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nACGTac\nGGNN\n>seq2\nCCCCRS\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    let counts = reader.genome_composition().unwrap();
+    assert_eq!(counts.total(), 16);
+    assert_eq!(counts.a.total(), 2);
+    assert_eq!(counts.c.total(), 6);
+    assert_eq!(counts.g.total(), 3);
+    assert_eq!(counts.t.total(), 1);
+    assert_eq!(counts.n.total(), 2);
+    assert_eq!(counts.s.total(), 1);
+    assert_eq!(counts.other.total(), 1);
+    assert_eq!(counts.masked(), 2);
+    // S counts as GC, as in gc_content
+    assert_eq!(counts.gc_fraction(), 10.0 / 14.0);
+    assert_eq!(reader.gc_content("seq2", 0, 6).unwrap(), 5.0 / 6.0);
+}
+
+#[test]
+fn test_fetch_htslib_region() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(
+        &fasta,
+        ">chr1\nACGTACGTAC\nGGGGCCCCTT\n>HLA:A*01\nAAAACCCCGG\n>chr2\nCCCCAAAA\n>chr2:1-4\nTTTT\n",
+    )
+    .unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+    let fetch = |region: &str| reader.fetch_htslib_region(region);
+
+    assert_eq!(fetch("chr1:2-5").unwrap(), "CGTA");
+    assert_eq!(fetch("chr1:1,1-1,4").unwrap(), "GGGG");
+    assert_eq!(fetch("chr1:17-").unwrap(), "CCTT");
+    assert_eq!(fetch("chr1:17").unwrap(), "CCTT");
+    assert_eq!(fetch("chr1:-4").unwrap(), "ACGT");
+    assert_eq!(fetch("chr1:0.005k-0.008k").unwrap(), "ACGT");
+    assert_eq!(fetch("chr1:1.000000000000000000000000-4").unwrap(), "ACGT");
+    assert_eq!(fetch("chr1").unwrap().len(), 20);
+
+    // Names containing colons, quoted or not
+    assert_eq!(fetch("{HLA:A*01}:1-4").unwrap(), "AAAA");
+    assert_eq!(fetch("HLA:A*01:1-4").unwrap(), "AAAA");
+    assert_eq!(fetch("HLA:A*01").unwrap(), "AAAACCCCGG");
+
+    // "chr2:1-4" names a sequence and a region of chr2; quoting resolves it
+    assert!(matches!(
+        fetch("chr2:1-4"),
+        Err(FastaError::InvalidRegion(_))
+    ));
+    assert_eq!(fetch("{chr2:1-4}").unwrap(), "TTTT");
+    assert_eq!(fetch("{chr2}:1-4").unwrap(), "CCCC");
+
+    // Coordinates beyond the int64 range are rejected rather than wrapping
+    let out_of_range = [
+        "chr1:99999999999999999999",
+        "chr1:1e40",
+        "chr1:1-99999999999999999999",
+        "chr1:-99999999999999999999",
+        "chr1:9223372036854775807k",
+    ];
+    for malformed in ["chr1:5-2", "chr1:1-x", "nope:1-2", "{chr1", "{chr1}x"]
+        .into_iter()
+        .chain(out_of_range)
+    {
+        assert!(
+            matches!(fetch(malformed), Err(FastaError::InvalidRegion(_))),
+            "{}",
+            malformed
+        );
+    }
+}
+
+#[test]
+fn test_index_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("test.fa");
+    std::fs::write(&fasta, ">seq1\nATCGATCGATCGATCG\n").unwrap();
+    std::fs::write(dir.path().join("test.fa.fai"), "seq1\t16\t6\t16\t17\n").unwrap();
+    let fastq = dir.path().join("test.fq");
+    std::fs::write(&fastq, "@seq1\nATCGATCGATCGATCG\n+\nIIIIIIIIIIIIIIII\n").unwrap();
+    std::fs::write(dir.path().join("test.fq.fai"), "seq1\t16\t6\t16\t17\t25\n").unwrap();
+
+    let index = FastaIndex::new(fasta.to_str().unwrap(), FastaFormat::Fasta).unwrap();
+    assert_eq!(index.format(), FastaFormat::Fasta);
+    assert!(!index.is_fastq());
+
+    let index = FastaIndex::new(fastq.to_str().unwrap(), FastaFormat::Fastq).unwrap();
+    assert_eq!(index.clone().format(), FastaFormat::Fastq);
+    assert!(index.is_fastq());
+
+    // Detection from the first non-whitespace byte
+    let index = FastaIndex::new_auto(fasta.to_str().unwrap()).unwrap();
+    assert_eq!(index.format(), FastaFormat::Fasta);
+    let index = FastaIndex::new_auto(fastq.to_str().unwrap()).unwrap();
+    assert_eq!(index.format(), FastaFormat::Fastq);
+
+    let other = dir.path().join("test.txt");
+    std::fs::write(&other, "\n  not a sequence file\n").unwrap();
+    match FastaIndex::new_auto(other.to_str().unwrap()).unwrap_err() {
+        FastaError::IndexLoadError(msg) => assert!(msg.contains("cannot detect format")),
+        e => panic!("Expected IndexLoadError, got {:?}", e),
+    }
+}
+
 #[test]
 fn test_region_parsing() {
     let fasta_file = create_test_fasta();
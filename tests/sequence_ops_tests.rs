@@ -0,0 +1,1807 @@
+use faigz_rs::{
+    fetch_regions_parallel, Anchor, FastaError, FastaFormat, FastaIndex, FastaReader,
+    IndexCreation, NucleicAcid, PartialWindow, ReaderPool, Strand, SyncFastaReader,
+};
+use std::fs;
+use std::io::Write;
+use std::time::Duration;
+
+const FASTA_FILE: &str = "scerevisiae8.fa.gz";
+
+fn open_test_reader() -> Option<(FastaIndex, FastaReader)> {
+    if fs::metadata(FASTA_FILE).is_err() {
+        eprintln!("Test file {} not found, skipping test", FASTA_FILE);
+        return None;
+    }
+
+    let index =
+        FastaIndex::new(FASTA_FILE, FastaFormat::Fasta).expect("Failed to load FASTA index");
+    let reader = FastaReader::new(&index).expect("Failed to create FASTA reader");
+    Some((index, reader))
+}
+
+#[test]
+fn test_validate_offsets() {
+    if fs::metadata(FASTA_FILE).is_err() {
+        return;
+    }
+    assert!(FastaIndex::builder()
+        .validate_offsets(true)
+        .open(FASTA_FILE)
+        .is_ok());
+
+    // A truncated copy without its .gzi: the block walk hits the cut block
+    let dir = tempfile::tempdir().unwrap();
+    let truncated = dir.path().join("truncated.fa.gz");
+    let data = fs::read(FASTA_FILE).unwrap();
+    fs::write(&truncated, &data[..data.len() - 1000]).unwrap();
+    fs::copy(
+        format!("{}.fai", FASTA_FILE),
+        dir.path().join("truncated.fa.gz.fai"),
+    )
+    .unwrap();
+
+    let path = truncated.to_str().unwrap();
+    assert!(FastaIndex::new(path, FastaFormat::Fasta).is_ok());
+    match FastaIndex::builder().validate_offsets(true).open(path) {
+        Err(FastaError::InconsistentIndex(msg)) => assert!(msg.contains("BGZF block")),
+        other => panic!("Expected InconsistentIndex, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_fetch_seq_lines() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let sequence = reader.fetch_seq(&seq_name, 100, 350).unwrap();
+    let lines = reader.fetch_seq_lines(&seq_name, 100, 350, 60).unwrap();
+
+    assert_eq!(lines.len(), 5);
+    assert!(lines[..4].iter().all(|line| line.len() == 60));
+    assert_eq!(lines[4].len(), 10);
+    assert_eq!(lines.concat(), sequence);
+
+    assert!(reader.fetch_seq_lines(&seq_name, 100, 350, 0).is_err());
+}
+
+#[test]
+fn test_fetch_seq_bytes() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let bytes = reader.fetch_seq_bytes(&seq_name, 100, 350).unwrap();
+    assert_eq!(bytes.len(), 250);
+    assert_eq!(
+        bytes,
+        reader.fetch_seq(&seq_name, 100, 350).unwrap().into_bytes()
+    );
+    assert_eq!(
+        &*reader.fetch_seq_shared(&seq_name, 100, 350).unwrap(),
+        reader.fetch_seq(&seq_name, 100, 350).unwrap()
+    );
+
+    assert!(matches!(
+        reader.fetch_seq_bytes("nonexistent", 0, 10),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+}
+
+#[test]
+fn test_fetch_error_kinds() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    assert!(matches!(
+        reader.fetch_seq("nonexistent", 0, 10),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+    match reader.fetch_seq(&seq_name, length + 10, length + 20) {
+        Err(FastaError::InvalidCoordinates {
+            seqname,
+            start,
+            end,
+        }) => assert_eq!(
+            (seqname, start, end),
+            (seq_name.clone(), length + 10, length + 20)
+        ),
+        other => panic!("Expected InvalidCoordinates, got {:?}", other),
+    }
+
+    // Malformed coordinates are rejected before reaching the C reader
+    for (start, end) in [(100, 50), (-10, 20), (0, -1)] {
+        assert!(matches!(
+            reader.fetch_seq(&seq_name, start, end),
+            Err(FastaError::InvalidCoordinates { .. })
+        ));
+    }
+    assert_eq!(reader.fetch_seq(&seq_name, 100, 100).unwrap(), "");
+}
+
+#[test]
+fn test_fetch_regions() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let first = index.sequence_name(0).unwrap();
+    let second = index.sequence_name(1).unwrap();
+
+    let regions = vec![
+        (first.clone(), 100, 200),
+        (second.clone(), 0, 50),
+        ("nonexistent".to_string(), 0, 10),
+        (first.clone(), 500, 400),
+        (first.clone(), 1000, 1100),
+    ];
+    let results = reader.fetch_regions(&regions);
+
+    assert_eq!(results.len(), regions.len());
+    for (result, (name, start, end)) in results.iter().zip(&regions) {
+        match reader.fetch_seq(name, *start, *end) {
+            Ok(expected) => assert_eq!(result.as_ref().unwrap(), &expected),
+            Err(_) => assert!(result.is_err()),
+        }
+    }
+    assert!(matches!(results[2], Err(FastaError::SequenceNotFound(_))));
+    assert!(matches!(
+        results[3],
+        Err(FastaError::InvalidCoordinates {
+            start: 500,
+            end: 400,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_fetch_regions_parallel() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let names = index.sequence_names();
+
+    let regions: Vec<_> = (0..40)
+        .map(|i| {
+            (
+                names[i % names.len()].clone(),
+                i as i64 * 100,
+                i as i64 * 100 + 60,
+            )
+        })
+        .collect();
+    let expected = reader.fetch_regions(&regions);
+
+    for threads in [0, 1, 3, 64] {
+        let results = fetch_regions_parallel(&index, &regions, threads);
+        assert_eq!(results.len(), regions.len());
+        for (result, expected) in results.iter().zip(&expected) {
+            assert_eq!(result.as_ref().unwrap(), expected.as_ref().unwrap());
+        }
+    }
+    assert!(fetch_regions_parallel(&index, &[], 4).is_empty());
+}
+
+#[test]
+fn test_gc_content() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let sequence = reader.fetch_seq(&seq_name, 2000, 3000).unwrap();
+    let gc = sequence.bytes().filter(|b| b"GCSgcs".contains(b)).count();
+    let called = sequence.bytes().filter(|b| !b"Nn".contains(b)).count();
+
+    let content = reader.gc_content(&seq_name, 2000, 3000).unwrap();
+    assert!((content - gc as f64 / called as f64).abs() < 1e-12);
+    assert_eq!(reader.gc_content(&seq_name, 2000, 2000).unwrap(), 0.0);
+}
+
+#[test]
+fn test_base_composition() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let sequence = reader
+        .fetch_seq(&seq_name, 0, 5000)
+        .unwrap()
+        .to_ascii_uppercase();
+    let count = |base: u8| sequence.bytes().filter(|&b| b == base).count() as u64;
+
+    let composition = reader.base_composition(&seq_name, 0, 5000).unwrap();
+    assert_eq!(
+        composition[..4],
+        [count(b'A'), count(b'C'), count(b'G'), count(b'T')]
+    );
+    assert_eq!(composition.iter().sum::<u64>(), 5000);
+    assert_eq!(reader.count_ns(&seq_name, 0, 5000).unwrap(), count(b'N'));
+}
+
+#[test]
+fn test_fetch_seq_clamped() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    assert_eq!(
+        reader
+            .fetch_seq_clamped(&seq_name, length - 10, length + 1_000_000)
+            .unwrap(),
+        reader.fetch_seq(&seq_name, length - 10, length).unwrap()
+    );
+    assert_eq!(
+        reader.fetch_seq_clamped(&seq_name, -5, 10).unwrap(),
+        reader.fetch_seq(&seq_name, 0, 10).unwrap()
+    );
+    assert_eq!(
+        reader
+            .fetch_seq_clamped(&seq_name, 999_999_999, 1_000_000_000)
+            .unwrap(),
+        ""
+    );
+    assert!(reader.fetch_seq_clamped("nonexistent", 0, 10).is_err());
+}
+
+#[test]
+fn test_fetch_seq_into() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let mut buf = b"stale".to_vec();
+    let written = reader
+        .fetch_seq_into(&seq_name, 100, 350, &mut buf)
+        .unwrap();
+    assert_eq!(written, 250);
+    assert_eq!(buf, reader.fetch_seq_bytes(&seq_name, 100, 350).unwrap());
+
+    // Reuse keeps the allocation and replaces the contents
+    let capacity = buf.capacity();
+    let written = reader.fetch_seq_into(&seq_name, 0, 50, &mut buf).unwrap();
+    assert_eq!(written, 50);
+    assert_eq!(buf, reader.fetch_seq_bytes(&seq_name, 0, 50).unwrap());
+    assert_eq!(buf.capacity(), capacity);
+
+    assert!(reader
+        .fetch_seq_into("nonexistent", 0, 10, &mut buf)
+        .is_err());
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_windows() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+    assert_eq!(length, 230218);
+
+    let tiles: Vec<(i64, String)> = reader
+        .windows(&seq_name, 50_000, 50_000, PartialWindow::Truncate)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let starts: Vec<i64> = tiles.iter().map(|(start, _)| *start).collect();
+    assert_eq!(starts, vec![0, 50_000, 100_000, 150_000, 200_000]);
+    assert_eq!(tiles[4].1.len(), 30_218);
+    let joined: String = tiles.into_iter().map(|(_, seq)| seq).collect();
+    assert_eq!(joined, reader.fetch_seq_all(&seq_name).unwrap());
+
+    let full: Vec<i64> = reader
+        .windows(&seq_name, 50_000, 50_000, PartialWindow::Skip)
+        .unwrap()
+        .map(|window| window.unwrap().0)
+        .collect();
+    assert_eq!(full, vec![0, 50_000, 100_000, 150_000]);
+
+    // Overlapping windows
+    let sliding: Vec<(i64, usize)> = reader
+        .windows(&seq_name, 100_000, 60_000, PartialWindow::Truncate)
+        .unwrap()
+        .map(|window| window.map(|(start, seq)| (start, seq.len())).unwrap())
+        .collect();
+    assert_eq!(
+        sliding,
+        vec![
+            (0, 100_000),
+            (60_000, 100_000),
+            (120_000, 100_000),
+            (180_000, 50_218)
+        ]
+    );
+    let skipped = reader
+        .windows(&seq_name, 100_000, 60_000, PartialWindow::Skip)
+        .unwrap()
+        .count();
+    assert_eq!(skipped, 3);
+    assert_eq!(
+        reader
+            .windows(&seq_name, length + 1, 1, PartialWindow::Skip)
+            .unwrap()
+            .count(),
+        0
+    );
+
+    assert!(matches!(
+        reader.windows(&seq_name, 0, 10, PartialWindow::Skip),
+        Err(FastaError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        reader.windows("nonexistent", 10, 10, PartialWindow::Skip),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+}
+
+#[test]
+fn test_iter_sequences() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+
+    let names: Vec<_> = index.iter_names().collect();
+    assert_eq!(names, index.sequence_names());
+
+    let mut sequences = reader.iter_sequences();
+    let (name, sequence) = sequences.next().unwrap().unwrap();
+    assert_eq!(name, names[0]);
+    assert_eq!(sequence.len() as i64, index.sequence_length(&name).unwrap());
+    assert_eq!(sequences.count(), names.len() - 1);
+}
+
+#[test]
+fn test_load_all_ordered() {
+    if fs::metadata(FASTA_FILE).is_err() {
+        return;
+    }
+
+    // Names out of alphabetical order come back in index order
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("unsorted.fa");
+    fs::write(&fasta, ">zeta\nACGT\n>alpha\nGG\n>mid\nTTTAA\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+    let loaded = reader.load_all_ordered().unwrap();
+    let names: Vec<_> = loaded.iter().map(|(name, _)| name.clone()).collect();
+    assert_eq!(names, index.sequence_names());
+    assert_eq!(names, ["zeta", "alpha", "mid"]);
+    assert_eq!(loaded[2].1, "TTTAA");
+
+    // A filtered index yields only its selection, still in index order
+    let all = FastaIndex::new(FASTA_FILE, FastaFormat::Fasta).unwrap();
+    let (first, second) = (all.sequence_name(0).unwrap(), all.sequence_name(1).unwrap());
+    let index = FastaIndex::new_filtered(FASTA_FILE, FastaFormat::Fasta, |name| {
+        name == second || name == first
+    })
+    .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+    let loaded = reader.load_all_ordered().unwrap();
+    let names: Vec<_> = loaded.iter().map(|(name, _)| name.clone()).collect();
+    assert_eq!(names, index.sequence_names());
+    assert_eq!(names, [first.clone(), second]);
+    assert_eq!(loaded[0].1, reader.fetch_seq_all(&first).unwrap());
+}
+
+#[test]
+fn test_records() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    let records = index.records();
+    assert_eq!(records.len(), index.num_sequences());
+    for (record, name) in records.iter().zip(index.sequence_names()) {
+        assert_eq!(record.name, name);
+        assert_eq!(Some(record.length), index.sequence_length(&name));
+    }
+
+    let total: i64 = records.iter().map(|record| record.length).sum();
+    assert_eq!(index.total_length(), total);
+    assert_eq!(index.clone().total_length(), total);
+
+    let lengths = index.length_map();
+    assert_eq!(lengths.len(), records.len());
+    for record in &records {
+        assert_eq!(lengths[&record.name], record.length);
+    }
+
+    assert_eq!(index.metadata().sequences, records);
+}
+
+#[test]
+fn test_write_sizes() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    let mut out = Vec::new();
+    index.write_sizes(&mut out).unwrap();
+    let expected: String = index
+        .records()
+        .iter()
+        .map(|record| format!("{}\t{}\n", record.name, record.length))
+        .collect();
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("genome.sizes");
+    index.write_sizes_to_path(&path).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+}
+
+#[test]
+fn test_fetch_record_fasta() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let (sequence, quality) = reader.fetch_record(&seq_name, 100, 200).unwrap();
+    assert_eq!(sequence, reader.fetch_seq(&seq_name, 100, 200).unwrap());
+    assert_eq!(quality, None);
+}
+
+#[test]
+fn test_fetch_record_named() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let record = reader.fetch_record_named(&seq_name, 100, 200).unwrap();
+    assert_eq!(record.name, format!("{}:101-200", seq_name));
+    assert_eq!(record.seq, reader.fetch_seq(&seq_name, 100, 200).unwrap());
+    assert_eq!(record.qual, None);
+
+    // The header round-trips through region parsing
+    assert_eq!(reader.fetch_region(&record.name).unwrap(), record.seq);
+}
+
+#[test]
+fn test_cli_extract_matches_fetch_region() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    // The CLI and the library read a single position as running to the end
+    let extract = |region: &str, one_based: bool| {
+        let mut command = std::process::Command::new(env!("CARGO_BIN_EXE_faigz"));
+        command.args(["extract", FASTA_FILE, region]);
+        if one_based {
+            command.arg("--one-based");
+        }
+        let output = command.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .skip(1)
+            .collect::<String>()
+    };
+    let single = format!("{}:{}", seq_name, length - 9);
+    let expected = reader.fetch_region(&single).unwrap();
+    assert_eq!(expected.len(), 10);
+    assert_eq!(extract(&single, true), expected);
+    assert_eq!(
+        extract(&format!("{}:{}", seq_name, length - 10), false),
+        expected
+    );
+}
+
+#[test]
+fn test_fetch_qual_scores_fasta() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    assert!(matches!(
+        reader.fetch_qual_scores(&seq_name, 0, 10),
+        Err(FastaError::QualityNotAvailable)
+    ));
+    assert!(matches!(
+        reader.mean_qual(&seq_name, 0, 10),
+        Err(FastaError::QualityNotAvailable)
+    ));
+    assert!(matches!(
+        reader.fetch_seq_qual_revcomp(&seq_name, 0, 10),
+        Err(FastaError::QualityNotAvailable)
+    ));
+}
+
+#[test]
+fn test_new_auto_bgzf() {
+    if fs::metadata(FASTA_FILE).is_err() {
+        eprintln!("Test file {} not found, skipping test", FASTA_FILE);
+        return;
+    }
+
+    let index = FastaIndex::new_auto(FASTA_FILE).expect("Failed to load FASTA index");
+    assert_eq!(index.format(), FastaFormat::Fasta);
+    assert!(index.num_sequences() > 0);
+}
+
+#[test]
+fn test_rank() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    for (i, name) in index.sequence_names().iter().enumerate() {
+        assert_eq!(index.rank(name), Some(i));
+        assert_eq!(index.sequence_name(i).as_deref(), Some(name.as_str()));
+    }
+    assert_eq!(index.rank("nonexistent"), None);
+}
+
+#[test]
+fn test_fetch_seq_as() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let dna = reader.fetch_seq(&seq_name, 0, 200).unwrap();
+    let rna = reader
+        .fetch_seq_as(&seq_name, 0, 200, NucleicAcid::Rna)
+        .unwrap();
+    assert_eq!(rna, dna.replace('T', "U").replace('t', "u"));
+
+    let back = reader
+        .fetch_seq_as(&seq_name, 0, 200, NucleicAcid::Dna)
+        .unwrap();
+    assert_eq!(back, dna);
+}
+
+#[test]
+fn test_fetch_seq_revcomp() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let forward = reader.fetch_seq(&seq_name, 300, 420).unwrap();
+    let revcomp = reader.fetch_seq_revcomp(&seq_name, 300, 420).unwrap();
+    assert_eq!(revcomp.len(), forward.len());
+
+    let complemented: String = revcomp
+        .chars()
+        .rev()
+        .map(|c| match c {
+            'A' => 'T',
+            'C' => 'G',
+            'G' => 'C',
+            'T' => 'A',
+            'a' => 't',
+            'c' => 'g',
+            'g' => 'c',
+            't' => 'a',
+            other => other,
+        })
+        .collect();
+    assert_eq!(complemented, forward);
+}
+
+#[test]
+fn test_fetch_seq_complement() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let forward = reader.fetch_seq(&seq_name, 300, 420).unwrap();
+    let complement = reader.fetch_seq_complement(&seq_name, 300, 420).unwrap();
+    assert_eq!(complement.len(), forward.len());
+    for (base, comp) in forward.bytes().zip(complement.bytes()) {
+        let expected = match base.to_ascii_uppercase() {
+            b'A' => b'T',
+            b'C' => b'G',
+            b'G' => b'C',
+            b'T' => b'A',
+            other => other,
+        };
+        assert_eq!(comp.to_ascii_uppercase(), expected);
+        assert_eq!(comp.is_ascii_lowercase(), base.is_ascii_lowercase());
+    }
+
+    let revcomp = reader.fetch_seq_revcomp(&seq_name, 300, 420).unwrap();
+    assert_eq!(complement.chars().rev().collect::<String>(), revcomp);
+}
+
+#[test]
+fn test_fetch_stranded() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    assert_eq!(
+        reader
+            .fetch_stranded(&seq_name, 300, 420, Strand::Forward)
+            .unwrap(),
+        reader.fetch_seq(&seq_name, 300, 420).unwrap()
+    );
+    assert_eq!(
+        reader
+            .fetch_stranded(&seq_name, 300, 420, Strand::Reverse)
+            .unwrap(),
+        reader.fetch_seq_revcomp(&seq_name, 300, 420).unwrap()
+    );
+}
+
+#[test]
+fn test_fetch_spliced() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let exons = [(500, 560), (100, 150), (300, 320)];
+    let joined = [(100, 150), (300, 320), (500, 560)]
+        .iter()
+        .map(|&(start, end)| reader.fetch_seq(&seq_name, start, end).unwrap())
+        .collect::<String>();
+
+    let forward = reader
+        .fetch_spliced(&seq_name, &exons, Strand::Forward)
+        .unwrap();
+    assert_eq!(forward, joined);
+
+    // Reverse strand: exons last to first, each reverse-complemented
+    let reverse = reader
+        .fetch_spliced(&seq_name, &exons, Strand::Reverse)
+        .unwrap();
+    let expected = [(500, 560), (300, 320), (100, 150)]
+        .iter()
+        .map(|&(start, end)| reader.fetch_seq_revcomp(&seq_name, start, end).unwrap())
+        .collect::<String>();
+    assert_eq!(reverse, expected);
+
+    assert_eq!(
+        reader
+            .fetch_spliced(&seq_name, &[], Strand::Reverse)
+            .unwrap(),
+        ""
+    );
+    assert!(matches!(
+        reader.fetch_spliced(&seq_name, &[(100, 150), (60, 50)], Strand::Forward),
+        Err(FastaError::InvalidCoordinates { .. })
+    ));
+}
+
+#[test]
+fn test_fetch_seq_circular() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let tail = reader.fetch_seq(&seq_name, length - 50, length).unwrap();
+    let head = reader.fetch_seq(&seq_name, 0, 20).unwrap();
+    let expected = format!("{}{}", tail, head);
+
+    // start > end wraps past the origin
+    let wrapped = reader
+        .fetch_seq_circular(&seq_name, length - 50, 20)
+        .unwrap();
+    assert_eq!(wrapped, expected);
+
+    // end beyond the length wraps as well
+    let wrapped = reader
+        .fetch_seq_circular(&seq_name, length - 50, length + 20)
+        .unwrap();
+    assert_eq!(wrapped, expected);
+
+    // Linear regions are unaffected
+    let linear = reader.fetch_seq_circular(&seq_name, 10, 40).unwrap();
+    assert_eq!(linear, reader.fetch_seq(&seq_name, 10, 40).unwrap());
+
+    // Regions covering part of the sequence twice are rejected
+    assert!(reader
+        .fetch_seq_circular(&seq_name, 10, 20 + length)
+        .is_err());
+}
+
+#[test]
+fn test_fetch_fixed() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let fixed = reader
+        .fetch_fixed(&seq_name, 1000, 11, b'N', Anchor::Center)
+        .unwrap();
+    assert_eq!(fixed, reader.fetch_seq(&seq_name, 995, 1006).unwrap());
+
+    // Near the start, real bases stay right of the padding
+    let fixed = reader
+        .fetch_fixed(&seq_name, 2, 10, b'N', Anchor::Center)
+        .unwrap();
+    let head = reader.fetch_seq(&seq_name, 0, 7).unwrap();
+    assert_eq!(fixed, format!("NNN{}", head));
+
+    let fixed = reader
+        .fetch_fixed(&seq_name, length - 4, 10, b'-', Anchor::Left)
+        .unwrap();
+    let tail = reader.fetch_seq(&seq_name, length - 4, length).unwrap();
+    assert_eq!(fixed, format!("{}------", tail));
+
+    let fixed = reader
+        .fetch_fixed(&seq_name, 3, 5, b'N', Anchor::Right)
+        .unwrap();
+    let head = reader.fetch_seq(&seq_name, 0, 3).unwrap();
+    assert_eq!(fixed, format!("NN{}", head));
+
+    // Windows entirely outside the sequence are all padding
+    let fixed = reader
+        .fetch_fixed(&seq_name, length + 100, 8, b'N', Anchor::Left)
+        .unwrap();
+    assert_eq!(fixed, "NNNNNNNN");
+
+    assert!(reader
+        .fetch_fixed(&seq_name, 0, -1, b'N', Anchor::Center)
+        .is_err());
+    assert!(matches!(
+        reader.fetch_fixed(&seq_name, i64::MIN, 10, b'N', Anchor::Right),
+        Err(FastaError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_fetch_seq_2bit() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    // Packing requires a region of unambiguous bases
+    let sequence = reader.fetch_seq(&seq_name, 1000, 1010).unwrap();
+    assert!(sequence.bytes().all(|b| b"ACGTacgt".contains(&b)));
+
+    let (packed, count) = reader.fetch_seq_2bit(&seq_name, 1000, 1010).unwrap();
+    assert_eq!(count, 10);
+    assert_eq!(packed.len(), 3);
+
+    let unpacked: String = (0..count)
+        .map(|i| b"ACGT"[((packed[i / 4] >> (6 - 2 * (i % 4))) & 3) as usize] as char)
+        .collect();
+    assert_eq!(unpacked, sequence.to_ascii_uppercase());
+}
+
+#[test]
+fn test_genome_chunks() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    let chunk_size = 100_000;
+    let chunks: Vec<_> = index.genome_chunks(chunk_size).collect();
+
+    for name in index.sequence_names() {
+        let length = index.sequence_length(&name).unwrap();
+        let tiles: Vec<_> = chunks.iter().filter(|(n, _, _)| *n == name).collect();
+
+        // Chunks tile the sequence contiguously without overlap
+        assert_eq!(tiles.first().unwrap().1, 0);
+        assert_eq!(tiles.last().unwrap().2, length);
+        for pair in tiles.windows(2) {
+            assert_eq!(pair[0].2, pair[1].1);
+        }
+        assert!(tiles.iter().all(|(_, s, e)| e - s <= chunk_size));
+    }
+}
+
+#[test]
+fn test_sequences_in_global_range() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+    let first = index.sequence_name(0).unwrap();
+    let second = index.sequence_name(1).unwrap();
+    let first_len = index.sequence_length(&first).unwrap();
+    let second_len = index.sequence_length(&second).unwrap();
+
+    assert_eq!(
+        index.sequences_in_global_range(10, 20),
+        vec![(first.clone(), 10, 20)]
+    );
+
+    // A span crossing the first boundary splits into two local ranges
+    assert_eq!(
+        index.sequences_in_global_range(first_len - 5, first_len + 5),
+        vec![(first, first_len - 5, first_len), (second.clone(), 0, 5)]
+    );
+
+    assert_eq!(
+        index.sequences_in_global_range(first_len, first_len + second_len),
+        vec![(second, 0, second_len)]
+    );
+    assert!(index.sequences_in_global_range(20, 10).is_empty());
+}
+
+#[test]
+fn test_read_buffer_size() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let buffered = FastaIndex::builder()
+        .format(FastaFormat::Fasta)
+        .read_buffer_size(1 << 20)
+        .open(FASTA_FILE)
+        .unwrap();
+    let buffered_reader = FastaReader::new(&buffered).unwrap();
+    assert_eq!(
+        buffered_reader.fetch_seq(&seq_name, 1000, 5000).unwrap(),
+        reader.fetch_seq(&seq_name, 1000, 5000).unwrap()
+    );
+
+    match FastaIndex::builder().read_buffer_size(0).open(FASTA_FILE) {
+        Err(FastaError::InvalidParameter(_)) => (),
+        other => panic!("Expected InvalidParameter, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_mmap_falls_back_for_bgzip() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let mapped = FastaIndex::builder().mmap(true).open(FASTA_FILE).unwrap();
+    let mapped_reader = FastaReader::new(&mapped).unwrap();
+    assert_eq!(
+        mapped_reader.fetch_seq(&seq_name, 1000, 5000).unwrap(),
+        reader.fetch_seq(&seq_name, 1000, 5000).unwrap()
+    );
+}
+
+#[test]
+fn test_new_filtered() {
+    if fs::metadata(FASTA_FILE).is_err() {
+        eprintln!("Test file {} not found, skipping test", FASTA_FILE);
+        return;
+    }
+
+    let index = FastaIndex::new_filtered(FASTA_FILE, FastaFormat::Fasta, |name| {
+        name.ends_with("#chrMT")
+    })
+    .expect("Failed to load FASTA index");
+
+    let names = index.sequence_names();
+    assert!(!names.is_empty());
+    assert!(names.iter().all(|name| name.ends_with("#chrMT")));
+    assert_eq!(index.num_sequences(), names.len());
+    assert_eq!(index.rank(&names[1]), Some(1));
+
+    let hidden = "SGDref#1#chrI";
+    assert!(!index.has_sequence(hidden));
+    assert!(index.sequence_length(hidden).is_none());
+
+    let reader = FastaReader::new(&index).unwrap();
+    assert!(reader.fetch_seq(&names[0], 0, 10).is_ok());
+    assert!(reader.fetch_seq(hidden, 0, 10).is_err());
+}
+
+#[test]
+fn test_count_mismatches() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let reference = reader.fetch_seq(&seq_name, 500, 520).unwrap();
+    let mut query = reference.to_ascii_lowercase().into_bytes();
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 500, &query, true, None)
+            .unwrap(),
+        0
+    );
+
+    // Substitute two bases and mask a third with N
+    for i in [3, 11] {
+        query[i] = if query[i] == b'a' { b'c' } else { b'a' };
+    }
+    query[15] = b'N';
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 500, &query, true, None)
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 500, &query, false, None)
+            .unwrap(),
+        3
+    );
+
+    // Counting stops one past the limit
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 500, &query, false, Some(1))
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 500, &query, false, Some(5))
+            .unwrap(),
+        3
+    );
+
+    // Queries longer than one fetch chunk are compared in full
+    let long = reader.fetch_seq(&seq_name, 0, 10_000).unwrap().into_bytes();
+    assert_eq!(
+        reader
+            .count_mismatches(&seq_name, 0, &long, false, None)
+            .unwrap(),
+        0
+    );
+
+    // Windows running past the end of the sequence are rejected
+    assert!(reader
+        .count_mismatches(&seq_name, length - 5, &query, true, None)
+        .is_err());
+}
+
+#[test]
+fn test_strict_validation() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let reader = FastaReader::new(&index)
+        .unwrap()
+        .with_strict_validation(true);
+
+    // Regions spanning many lines come back without line terminators
+    let seq = reader.fetch_seq(&seq_name, 80, 1080).unwrap();
+    assert_eq!(seq.len(), 1000);
+    assert!(reader.fetch_seq_all(&seq_name).is_ok());
+    assert_eq!(
+        reader
+            .fetch_seq(&seq_name, length - 3, length + 10)
+            .unwrap()
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_write_filtered() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+
+    let mut out = Vec::new();
+    let written = reader
+        .write_filtered(&mut out, |name, _| name.ends_with("#chrMT"), 70)
+        .unwrap();
+    assert_eq!(written, out.len() as u64);
+
+    let text = String::from_utf8(out).unwrap();
+    let mut records = 0;
+    for record in text.split('>').skip(1) {
+        let mut lines = record.lines();
+        let name = lines.next().unwrap();
+        assert!(name.ends_with("#chrMT"));
+
+        let lines: Vec<&str> = lines.collect();
+        assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 70));
+        assert_eq!(lines.concat(), reader.fetch_seq_all(name).unwrap());
+        records += 1;
+    }
+    assert_eq!(
+        records,
+        index
+            .sequence_names()
+            .iter()
+            .filter(|name| name.ends_with("#chrMT"))
+            .count()
+    );
+}
+
+#[test]
+fn test_fetch_region_open_ended() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let to_end = reader
+        .fetch_region(&format!("{}:{}", seq_name, length - 99))
+        .unwrap();
+    assert_eq!(
+        to_end,
+        reader.fetch_seq(&seq_name, length - 100, length).unwrap()
+    );
+
+    let trailing_dash = reader
+        .fetch_region(&format!("{}:{}-", seq_name, length - 99))
+        .unwrap();
+    assert_eq!(trailing_dash, to_end);
+
+    let from_start = reader.fetch_region(&format!("{}:-200", seq_name)).unwrap();
+    assert_eq!(from_start, reader.fetch_seq(&seq_name, 0, 200).unwrap());
+
+    for malformed in ["{}:abc-", "{}:10-x"] {
+        let region = malformed.replace("{}", &seq_name);
+        assert!(matches!(
+            reader.fetch_region(&region),
+            Err(FastaError::InvalidRegion(_))
+        ));
+    }
+}
+
+#[test]
+fn test_fetch_region_one_based() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let first_ten = reader.fetch_region(&format!("{}:1-10", seq_name)).unwrap();
+    assert_eq!(first_ten.len(), 10);
+    assert_eq!(first_ten, reader.fetch_seq(&seq_name, 0, 10).unwrap());
+
+    let single = reader.fetch_region(&format!("{}:5-5", seq_name)).unwrap();
+    assert_eq!(single, reader.fetch_seq(&seq_name, 4, 5).unwrap());
+}
+
+#[test]
+fn test_fetch_region_with_commas() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let expected = reader.fetch_seq(&seq_name, 1_000, 2_500).unwrap();
+
+    let region = format!("{}:1,001-2,500", seq_name);
+    assert_eq!(reader.fetch_region(&region).unwrap(), expected);
+    let region = format!("{}: 1,001 - 2,500 ", seq_name);
+    assert_eq!(reader.fetch_region(&region).unwrap(), expected);
+}
+
+#[test]
+fn test_fetch_bed_par() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let names = index.sequence_names();
+
+    let mut bed = tempfile::NamedTempFile::new().unwrap();
+    let mut expected = Vec::new();
+    for (i, name) in names.iter().take(20).enumerate() {
+        let start = 1000 + i as i64 * 80;
+        let strand = if i % 2 == 0 { "+" } else { "-" };
+        writeln!(
+            bed,
+            "{}\t{}\t{}\tfeature{}\t0\t{}",
+            name,
+            start,
+            start + 160,
+            i,
+            strand
+        )
+        .unwrap();
+        expected.push(reader.fetch_seq(name, start, start + 160).unwrap());
+    }
+    bed.flush().unwrap();
+
+    let results = index
+        .fetch_bed_par(bed.path().to_str().unwrap(), 4)
+        .unwrap();
+    assert_eq!(results.len(), expected.len());
+    for (i, (record, seq)) in results.iter().enumerate() {
+        assert_eq!(
+            record.name.as_deref(),
+            Some(format!("feature{}", i).as_str())
+        );
+        if record.strand == '-' {
+            assert_eq!(seq.len(), expected[i].len());
+            assert_ne!(seq, &expected[i]);
+        } else {
+            assert_eq!(seq, &expected[i]);
+        }
+    }
+}
+
+#[test]
+fn test_compression_info() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    assert!(index.is_compressed());
+    assert!(index.is_bgzf());
+    assert_eq!(
+        index.gzi_path(),
+        Some(std::path::PathBuf::from(format!("{}.gzi", FASTA_FILE)))
+    );
+}
+
+#[test]
+fn test_compressed_range() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+    let names = index.sequence_names();
+
+    let (first_start, first_end) = index.compressed_range(&names[0]).unwrap();
+    assert_eq!(first_start, 15); // first block, just past the header line
+    assert!(first_end > first_start);
+
+    // Consecutive sequences occupy increasing, non-overlapping ranges
+    let (second_start, _) = index.compressed_range(&names[1]).unwrap();
+    assert!(second_start > first_end);
+
+    assert!(index.compressed_range("nonexistent").is_err());
+}
+
+#[test]
+fn test_fetch_seq_lenient() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let (seq, clamped) = reader.fetch_seq_lenient(&seq_name, 0, 80).unwrap();
+    assert_eq!(seq.len(), 80);
+    assert!(!clamped);
+
+    let (seq, clamped) = reader
+        .fetch_seq_lenient(&seq_name, length - 40, length + 40)
+        .unwrap();
+    assert_eq!(
+        seq,
+        reader.fetch_seq(&seq_name, length - 40, length).unwrap()
+    );
+    assert!(clamped);
+
+    let (seq, clamped) = reader.fetch_seq_lenient(&seq_name, -50, 0).unwrap();
+    assert!(seq.is_empty());
+    assert!(clamped);
+}
+
+#[test]
+fn test_length_histogram() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    let bins = [100_000, 500_000, 1_000_000];
+    let counts = index.length_histogram(&bins);
+    assert_eq!(counts.len(), bins.len() + 1);
+    assert_eq!(counts.iter().sum::<usize>(), index.num_sequences());
+
+    let lengths: Vec<i64> = index
+        .sequence_names()
+        .iter()
+        .map(|name| index.sequence_length(name).unwrap())
+        .collect();
+    assert_eq!(counts[0], lengths.iter().filter(|&&l| l < 100_000).count());
+    assert_eq!(
+        counts[3],
+        lengths.iter().filter(|&&l| l >= 1_000_000).count()
+    );
+
+    assert_eq!(index.length_histogram(&[]), vec![index.num_sequences()]);
+}
+
+#[test]
+fn test_line_layout() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+
+    assert_eq!(index.line_bases("SGDref#1#chrI"), Some(80));
+    assert_eq!(index.line_width("SGDref#1#chrI"), Some(81));
+    assert_eq!(index.line_bases("nonexistent"), None);
+    assert_eq!(index.line_width("nonexistent"), None);
+
+    let filtered = FastaIndex::new_filtered(FASTA_FILE, FastaFormat::Fasta, |name| {
+        name != "SGDref#1#chrI"
+    })
+    .unwrap();
+    assert_eq!(filtered.line_bases("SGDref#1#chrI"), None);
+    assert_eq!(filtered.line_width("SGDref#1#chrII"), Some(81));
+}
+
+#[test]
+fn test_find_by_md5() {
+    if fs::metadata(FASTA_FILE).is_err() {
+        eprintln!("Test file {} not found, skipping test", FASTA_FILE);
+        return;
+    }
+
+    let index = FastaIndex::new_filtered(FASTA_FILE, FastaFormat::Fasta, |name| {
+        name.ends_with("#chrMT")
+    })
+    .expect("Failed to load FASTA index");
+
+    assert_eq!(
+        index
+            .find_by_md5("71c39cf065b8d574f636b654c274cf1b")
+            .unwrap()
+            .as_deref(),
+        Some("SGDref#1#chrMT")
+    );
+    // Cached digests serve later lookups, including through clones
+    assert_eq!(
+        index
+            .clone()
+            .find_by_md5("A5FFBA37199CAA4CF76EA17FC5373D7A")
+            .unwrap()
+            .as_deref(),
+        Some("S288C#1#chrMT")
+    );
+    assert_eq!(
+        index
+            .find_by_md5("d41d8cd98f00b204e9800998ecf8427e")
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_find_motif() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let region = reader
+        .fetch_seq(&seq_name, 0, 2000)
+        .unwrap()
+        .to_ascii_uppercase();
+    let expected: Vec<i64> = (0..region.len() - 1)
+        .filter(|&i| &region[i..i + 2] == "GG")
+        .map(|i| i as i64)
+        .collect();
+
+    // "NGG" matches one base before every GG with a preceding base
+    let pam = reader
+        .find_motif(&seq_name, 0, 2000, b"ngg", false)
+        .unwrap();
+    let shifted: Vec<i64> = expected
+        .iter()
+        .filter(|&&i| i > 0)
+        .map(|&i| i - 1)
+        .collect();
+    assert_eq!(pam, shifted);
+
+    // Positions are reported on the sequence, not relative to the window
+    let offset = reader
+        .find_motif(&seq_name, 1000, 2000, b"GG", false)
+        .unwrap();
+    let in_window: Vec<i64> = expected
+        .iter()
+        .copied()
+        .filter(|i| (1000..1999).contains(i))
+        .collect();
+    assert_eq!(offset, in_window);
+}
+
+#[test]
+fn test_find_motif_both_strands() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    // "CC" on the forward strand is "GG" on the reverse strand
+    let forward = reader.find_motif(&seq_name, 0, 2000, b"GG", false).unwrap();
+    let reverse = reader.find_motif(&seq_name, 0, 2000, b"CC", false).unwrap();
+    let both = reader.find_motif(&seq_name, 0, 2000, b"GG", true).unwrap();
+
+    let mut expected: Vec<i64> = forward.iter().chain(&reverse).copied().collect();
+    expected.sort_unstable();
+    assert_eq!(both, expected);
+
+    // A palindrome is reported once per site
+    let sites = reader
+        .find_motif(&seq_name, 0, 2000, b"AATT", false)
+        .unwrap();
+    assert!(!sites.is_empty());
+    assert_eq!(
+        reader
+            .find_motif(&seq_name, 0, 2000, b"AATT", true)
+            .unwrap(),
+        sites
+    );
+
+    assert!(matches!(
+        reader.find_motif(&seq_name, 0, 2000, b"", true),
+        Err(FastaError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_reader_sequence_metadata() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+
+    assert_eq!(reader.sequence_names(), index.sequence_names());
+    for name in &index.sequence_names()[..2] {
+        assert_eq!(reader.sequence_length(name), index.sequence_length(name));
+    }
+    assert_eq!(reader.sequence_length("no_such_sequence"), None);
+}
+
+#[test]
+fn test_try_fetch_seq() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    assert_eq!(
+        reader.try_fetch_seq(&seq_name, 100, 200).unwrap(),
+        Some(reader.fetch_seq(&seq_name, 100, 200).unwrap())
+    );
+    assert_eq!(
+        reader
+            .try_fetch_seq(&seq_name, length - 10, length + 10)
+            .unwrap(),
+        Some(reader.fetch_seq(&seq_name, length - 10, length).unwrap())
+    );
+    assert_eq!(reader.try_fetch_seq(&seq_name, 100, 100).unwrap(), None);
+    assert_eq!(
+        reader
+            .try_fetch_seq(&seq_name, length, length + 10)
+            .unwrap(),
+        None
+    );
+
+    assert!(matches!(
+        reader.try_fetch_seq("no_such_sequence", 0, 10),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+    assert!(matches!(
+        reader.try_fetch_seq(&seq_name, 200, 100),
+        Err(FastaError::InvalidCoordinates { .. })
+    ));
+}
+
+#[test]
+fn test_fetch_seq_unsigned() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let expected = reader.fetch_seq(&seq_name, 100, 200).unwrap();
+
+    assert_eq!(reader.fetch_seq_u64(&seq_name, 100, 200).unwrap(), expected);
+    assert_eq!(
+        reader.fetch_seq_usize(&seq_name, 100, 200).unwrap(),
+        expected
+    );
+    assert!(matches!(
+        reader.fetch_seq_u64(&seq_name, 0, u64::MAX),
+        Err(FastaError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_region_in_bounds() {
+    let Some((index, _reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    assert!(index.region_in_bounds(&seq_name, 0, length));
+    assert!(index.region_in_bounds(&seq_name, 100, 100));
+    assert!(!index.region_in_bounds(&seq_name, 0, length + 1));
+    assert!(!index.region_in_bounds(&seq_name, -1, 10));
+    assert!(!index.region_in_bounds(&seq_name, 20, 10));
+    assert!(!index.region_in_bounds("no_such_sequence", 0, 10));
+}
+
+#[test]
+fn test_try_clone() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let expected = reader.fetch_seq(&seq_name, 2000, 2500).unwrap();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let worker = reader.try_clone().unwrap();
+            let seq_name = seq_name.clone();
+            std::thread::spawn(move || worker.fetch_seq(&seq_name, 2000, 2500).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    // Settings carry over to the clone
+    let expired = reader
+        .try_clone()
+        .unwrap()
+        .with_timeout(Duration::ZERO)
+        .try_clone()
+        .unwrap();
+    assert!(expired.fetch_seq(&seq_name, 0, 10).is_err());
+}
+
+#[test]
+fn test_sync_reader_sharing() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    // Sequences near the start of the file keep the bgzip seeks short
+    let names = index.sequence_names()[..2].to_vec();
+    let expected: Vec<String> = names
+        .iter()
+        .map(|name| reader.fetch_seq(name, 1000, 1100).unwrap())
+        .collect();
+
+    let shared = std::sync::Arc::new(SyncFastaReader::new(&index).unwrap());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = std::sync::Arc::clone(&shared);
+            let names = names.clone();
+            std::thread::spawn(move || {
+                names
+                    .iter()
+                    .map(|name| shared.fetch_seq(name, 1000, 1100).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    let length = shared.with_reader(|r| r.fetch_seq_all(&names[0]).unwrap().len());
+    assert_eq!(length as i64, index.sequence_length(&names[0]).unwrap());
+
+    let reader = std::sync::Arc::try_unwrap(shared)
+        .ok()
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        reader.fetch_seq(&names[0], 1000, 1100).unwrap(),
+        expected[0]
+    );
+}
+
+#[test]
+fn test_reader_pool() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let names = index.sequence_names()[..2].to_vec();
+    let expected: Vec<String> = names
+        .iter()
+        .map(|name| reader.fetch_seq(name, 1000, 1100).unwrap())
+        .collect();
+
+    let pool = std::sync::Arc::new(ReaderPool::new(&index));
+    assert_eq!(pool.idle_readers(), 0);
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let pool = std::sync::Arc::clone(&pool);
+            let names = names.clone();
+            std::thread::spawn(move || {
+                names
+                    .iter()
+                    .map(|name| pool.fetch_seq(name, 1000, 1100).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+    let idle = pool.idle_readers();
+    assert!((1..=4).contains(&idle));
+
+    // Checked-out readers are reused rather than recreated
+    {
+        let first = pool.get().unwrap();
+        assert_eq!(first.fetch_seq(&names[0], 1000, 1100).unwrap(), expected[0]);
+        assert_eq!(pool.idle_readers(), idle - 1);
+    }
+    assert_eq!(pool.idle_readers(), idle);
+    assert!(matches!(
+        pool.fetch_seq("nonexistent", 0, 10),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+}
+
+#[test]
+fn test_fetch_with_timeout() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let timed = FastaReader::new(&index)
+        .unwrap()
+        .with_timeout(Duration::from_secs(30));
+    assert_eq!(
+        timed.fetch_seq(&seq_name, 0, 500).unwrap(),
+        reader.fetch_seq(&seq_name, 0, 500).unwrap()
+    );
+
+    let expired = FastaReader::new(&index)
+        .unwrap()
+        .with_timeout(Duration::ZERO);
+    match expired.fetch_seq_all(&seq_name) {
+        Err(FastaError::IoError(msg)) => assert_eq!(msg, "fetch timed out"),
+        other => panic!("Expected timeout, got {:?}", other.map(|s| s.len())),
+    }
+}
+
+#[test]
+fn test_fetch_range() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let seq = reader.fetch_range(&seq_name, 1000..2000).unwrap();
+    assert_eq!(seq.len(), 1000);
+    assert_eq!(seq, reader.fetch_seq(&seq_name, 1000, 2000).unwrap());
+
+    let (start, end) = (2000, 1000);
+    assert!(matches!(
+        reader.fetch_range(&seq_name, start..end),
+        Err(FastaError::InvalidCoordinates { .. })
+    ));
+}
+
+#[test]
+fn test_fetch_seq_masked() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let plain = reader
+        .fetch_seq(&seq_name, 100, 200)
+        .unwrap()
+        .to_uppercase();
+    // Unsorted, overlapping, empty and partially outside the region
+    let mask = [(190, 250), (110, 120), (115, 125), (0, 102), (150, 150)];
+    let masked = reader.fetch_seq_masked(&seq_name, 100, 200, &mask).unwrap();
+
+    assert_eq!(masked.len(), plain.len());
+    assert!(masked.eq_ignore_ascii_case(&plain));
+    for (i, base) in masked.bytes().enumerate() {
+        let pos = 100 + i as i64;
+        let inside = mask.iter().any(|&(s, e)| s <= pos && pos < e);
+        assert_eq!(base.is_ascii_lowercase(), inside, "position {}", pos);
+    }
+
+    assert_eq!(
+        reader.fetch_seq_masked(&seq_name, 100, 200, &[]).unwrap(),
+        reader.fetch_seq(&seq_name, 100, 200).unwrap()
+    );
+}
+
+#[test]
+fn test_fetch_seq_with_mask_bits() {
+    let dir = tempfile::tempdir().unwrap();
+    let fasta = dir.path().join("masked.fa");
+    fs::write(&fasta, ">chr1\nACgtNn\nacGT\n").unwrap();
+    let index = FastaIndex::builder()
+        .create(IndexCreation::IfMissing)
+        .mmap(true)
+        .open(fasta.to_str().unwrap())
+        .unwrap();
+    let reader = FastaReader::new(&index).unwrap();
+
+    let (sequence, mask) = reader.fetch_seq_with_mask_bits("chr1", 1, 9).unwrap();
+    assert_eq!(sequence, "CgtNnacG");
+    assert_eq!(mask.len(), sequence.len());
+    let lowercase: Vec<bool> = sequence.bytes().map(|b| b.is_ascii_lowercase()).collect();
+    assert_eq!(mask, lowercase);
+    assert_eq!(mask, [false, true, true, false, true, true, true, false]);
+}
+
+#[test]
+fn test_fetch_seq_hardmasked() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    let plain = reader.fetch_seq(&seq_name, 100, 200).unwrap();
+    let mask = [(150, 160), (90, 105), (155, 170)];
+    let masked = reader
+        .fetch_seq_hardmasked(&seq_name, 100, 200, &mask, b'N')
+        .unwrap();
+
+    let expected: String = plain
+        .chars()
+        .enumerate()
+        .map(|(i, base)| {
+            let pos = 100 + i as i64;
+            if mask.iter().any(|&(s, e)| s <= pos && pos < e) {
+                'N'
+            } else {
+                base
+            }
+        })
+        .collect();
+    assert_eq!(masked, expected);
+
+    let masked = reader
+        .fetch_seq_hardmasked(&seq_name, 100, 200, &mask, b'X')
+        .unwrap();
+    assert_eq!(&masked[50..60], "XXXXXXXXXX");
+
+    for mask_char in [0xC3, b'\n'] {
+        assert!(matches!(
+            reader.fetch_seq_hardmasked(&seq_name, 100, 200, &mask, mask_char),
+            Err(FastaError::InvalidParameter(_))
+        ));
+    }
+}
+
+#[test]
+fn test_fetch_protein() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+
+    for frame in 0..3u8 {
+        let protein = reader.fetch_protein(&seq_name, 1000, 1100, frame).unwrap();
+        assert_eq!(protein.len(), (100 - frame as usize) / 3);
+
+        let shifted = reader
+            .fetch_protein(&seq_name, 1000 + frame as i64, 1100, 0)
+            .unwrap();
+        assert_eq!(protein, shifted);
+    }
+
+    // Offset 1 is frame +2 of the whole-sequence six-frame translation
+    let length = index.sequence_length(&seq_name).unwrap();
+    let frames = reader.six_frame_translate(&seq_name).unwrap();
+    assert_eq!(
+        reader.fetch_protein(&seq_name, 0, length, 1).unwrap(),
+        frames[1]
+    );
+
+    let standard = reader.fetch_protein(&seq_name, 0, 3000, 0).unwrap();
+    let bacterial = reader
+        .fetch_protein_with_table(&seq_name, 0, 3000, 0, 11)
+        .unwrap();
+    assert_eq!(standard, bacterial);
+
+    assert!(matches!(
+        reader.fetch_protein(&seq_name, 0, 30, 3),
+        Err(FastaError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        reader.fetch_protein_with_table(&seq_name, 0, 30, 0, 7),
+        Err(FastaError::InvalidParameter(_))
+    ));
+    assert_eq!(reader.fetch_protein(&seq_name, 0, 1, 2).unwrap(), "");
+}
+
+#[test]
+fn test_fetch_seq_buf() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    let mut buf = reader.fetch_seq_buf(&seq_name, 0, length).unwrap();
+    assert_eq!(buf.len() as i64, length);
+    assert_eq!(
+        &buf[..],
+        &reader.fetch_seq_bytes(&seq_name, 0, length).unwrap()[..]
+    );
+
+    buf.make_ascii_lowercase();
+    assert!(!buf.iter().any(u8::is_ascii_uppercase));
+
+    assert!(reader.fetch_seq_buf(&seq_name, 10, 10).unwrap().is_empty());
+    assert!(matches!(
+        reader.fetch_seq_buf(&seq_name, 10, 5),
+        Err(FastaError::InvalidCoordinates { .. })
+    ));
+    assert!(matches!(
+        reader.fetch_seq_buf("nonexistent", 0, 10),
+        Err(FastaError::SequenceNotFound(_))
+    ));
+
+    // Buffers are handed back from the helper thread of a timed reader
+    let timed = FastaReader::new(&index)
+        .unwrap()
+        .with_timeout(Duration::from_secs(30));
+    assert_eq!(
+        &timed.fetch_seq_buf(&seq_name, 100, 200).unwrap()[..],
+        reader.fetch_seq(&seq_name, 100, 200).unwrap().as_bytes()
+    );
+}
+
+#[test]
+fn test_fetch_seq_checked() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap();
+
+    assert_eq!(
+        reader.fetch_seq_checked(&seq_name, 0, 100).unwrap().len(),
+        100
+    );
+
+    match reader.fetch_seq_checked(&seq_name, length - 10, length + 10) {
+        Err(FastaError::OutOfBounds {
+            length: reported,
+            suggested,
+            ..
+        }) => {
+            assert_eq!(reported, length);
+            assert_eq!(suggested, Some((length - 10, length)));
+        }
+        other => panic!("Expected OutOfBounds, got {:?}", other),
+    }
+
+    match reader.fetch_seq_checked(&seq_name, length + 5, length + 10) {
+        Err(FastaError::OutOfBounds { suggested, .. }) => assert_eq!(suggested, None),
+        other => panic!("Expected OutOfBounds, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_six_frame_translate() {
+    let Some((index, reader)) = open_test_reader() else {
+        return;
+    };
+    let seq_name = index.sequence_name(0).unwrap();
+    let length = index.sequence_length(&seq_name).unwrap() as usize;
+
+    let frames = reader.six_frame_translate(&seq_name).unwrap();
+    for (i, protein) in frames.iter().enumerate() {
+        assert_eq!(protein.len(), (length - i % 3) / 3, "frame {}", i);
+        assert!(protein
+            .bytes()
+            .all(|aa| aa.is_ascii_uppercase() || aa == b'*'));
+    }
+
+    assert!(reader.six_frame_translate("nonexistent").is_err());
+}